@@ -0,0 +1,140 @@
+/*!
+ * A compact representation of a collection of pais for fast hand algorithms.
+ */
+
+use crate::*;
+
+use core::ops::{Index, IndexMut};
+
+/**
+ * The amount of each kind of tile in a collection of tiles, indexed by `Pai::index`.
+ *
+ * Red fives are counted as normal fives.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TileCounts(pub [u8; 34]);
+
+impl TileCounts {
+    /**
+     * Counts with no tiles.
+     */
+    pub fn new() -> Self {
+        TileCounts([0; 34])
+    }
+
+    /**
+     * The amount of tiles of every kind.
+     */
+    pub fn total(&self) -> u8 {
+        self.0.iter().sum()
+    }
+
+    /**
+     * Adds one copy of a tile.
+     */
+    pub fn add(&mut self, tile: Pai) {
+        self[tile] += 1;
+    }
+
+    /**
+     * Removes one copy of a tile, returning whether there was one to remove.
+     */
+    pub fn remove(&mut self, tile: Pai) -> bool {
+        if self[tile] == 0 {
+            return false;
+        }
+        self[tile] -= 1;
+        true
+    }
+
+    /**
+     * The tiles in index order, with no red fives.
+     */
+    pub fn to_pais(&self) -> Vec<Pai> {
+        self.0
+            .iter()
+            .enumerate()
+            .flat_map(|(index, amount)| vec![Pai::from_index(index); usize::from(*amount)])
+            .collect()
+    }
+}
+
+impl Default for TileCounts {
+    fn default() -> Self {
+        TileCounts::new()
+    }
+}
+
+impl FromIterator<Pai> for TileCounts {
+    fn from_iter<I: IntoIterator<Item = Pai>>(tiles: I) -> Self {
+        let mut counts = TileCounts::new();
+        for tile in tiles {
+            counts.add(tile);
+        }
+        counts
+    }
+}
+
+impl From<&[Pai]> for TileCounts {
+    fn from(tiles: &[Pai]) -> Self {
+        tiles.iter().copied().collect()
+    }
+}
+
+impl From<TileCounts> for Vec<Pai> {
+    fn from(counts: TileCounts) -> Self {
+        counts.to_pais()
+    }
+}
+
+impl Index<Pai> for TileCounts {
+    type Output = u8;
+
+    fn index(&self, tile: Pai) -> &u8 {
+        &self.0[tile.index()]
+    }
+}
+
+impl IndexMut<Pai> for TileCounts {
+    fn index_mut(&mut self, tile: Pai) -> &mut u8 {
+        &mut self.0[tile.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn every_index_round_trips() {
+        for index in 0..34 {
+            assert_eq!(Pai::from_index(index).index(), index);
+        }
+    }
+
+    #[test]
+    fn counts_ignore_red_fives() {
+        let tiles = tilestring_to_pais("055m").unwrap();
+        let counts: TileCounts = tiles.iter().copied().collect();
+        assert_eq!(counts[tiles[0]], 3);
+        assert_eq!(counts.total(), 3);
+    }
+
+    #[test]
+    fn converts_back_to_sorted_pais() {
+        let tiles = tilestring_to_pais("7z9s1p3m1p").unwrap();
+        let counts = TileCounts::from(tiles.as_slice());
+        assert_eq!(counts.to_pais(), tilestring_to_pais("3m11p9s7z").unwrap());
+    }
+
+    #[test]
+    fn removing_a_missing_tile_fails() {
+        let tile = "1z".parse().unwrap();
+        let mut counts = TileCounts::new();
+        assert!(!counts.remove(tile));
+        counts.add(tile);
+        assert!(counts.remove(tile));
+        assert_eq!(counts.total(), 0);
+    }
+}
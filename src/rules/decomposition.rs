@@ -0,0 +1,285 @@
+/*!
+ * Functions for splitting a complete hand into its groups and pair.
+ */
+
+use crate::*;
+
+/**
+ * A group of tiles that has not been committed by a call.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Group {
+    /**
+     * The type of group.
+     */
+    pub mentsu: Mentsu,
+
+    /**
+     * For sets, this is the tile that is represented.
+     * For sequences, this is the lowest rank tile that is represented.
+     */
+    pub tile: Pai,
+}
+
+/**
+ * One way of reading a complete hand.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Decomposition {
+    /**
+     * Groups and a pair, which together with the melds make four groups and a pair.
+     */
+    Regular { groups: Vec<Group>, jantou: Pai },
+
+    /**
+     * Seven distinct pairs.
+     */
+    Chiitoitsu { pairs: Vec<Pai> },
+
+    /**
+     * One of each terminal and honor, plus a second copy of one of them.
+     */
+    KokushiMusou { jantou: Pai },
+}
+
+/**
+ * Every distinct way of reading a collection of tiles as a complete hand.
+ *
+ * The tiles in the result do not record red dora, so those should be counted from the
+ * original tiles.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ */
+pub fn decompose(free_tiles: impl IntoIterator<Item = Pai>, amt_melds: u8) -> Vec<Decomposition> {
+    decompose_with_ruleset(free_tiles, amt_melds, &Ruleset::default())
+}
+
+/**
+ * Every distinct way of reading a collection of tiles as a complete hand under the given rules.
+ *
+ * When quads count as two pairs of seven pairs, such a quad appears twice in the pairs.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn decompose_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    amt_melds: u8,
+    ruleset: &Ruleset,
+) -> Vec<Decomposition> {
+    let mut counts = free_tiles.into_iter().collect::<TileCounts>().0;
+    let mut decompositions = vec![];
+    if amt_melds > 4 {
+        return decompositions;
+    }
+
+    for jantou_index in 0..34 {
+        if counts[jantou_index] < 2 {
+            continue;
+        }
+        counts[jantou_index] -= 2;
+        let mut found = vec![];
+        search_groups(&mut counts, 4 - amt_melds, &mut vec![], &mut found);
+        counts[jantou_index] += 2;
+        decompositions.extend(found.into_iter().map(|groups| Decomposition::Regular {
+            groups,
+            jantou: Pai::from_index(jantou_index),
+        }));
+    }
+
+    if amt_melds == 0 {
+        let pair_sizes: &[u8] = if ruleset.chiitoitsu_quads_as_two_pairs {
+            &[2, 4]
+        } else {
+            &[2]
+        };
+        let pair_indices: Vec<usize> = (0..34)
+            .flat_map(|i| vec![i; usize::from(counts[i] / 2)])
+            .collect();
+        if pair_indices.len() == 7 && counts.iter().all(|c| *c == 0 || pair_sizes.contains(c)) {
+            decompositions.push(Decomposition::Chiitoitsu {
+                pairs: pair_indices.into_iter().map(Pai::from_index).collect(),
+            });
+        }
+
+        let amt_tiles: u8 = counts.iter().sum();
+        if amt_tiles == 14 && YAOCHUUHAI_INDICES.iter().all(|i| counts[*i] >= 1) {
+            if let Some(jantou_index) = YAOCHUUHAI_INDICES.iter().find(|i| counts[**i] == 2) {
+                decompositions.push(Decomposition::KokushiMusou {
+                    jantou: Pai::from_index(*jantou_index),
+                });
+            }
+        }
+    }
+
+    decompositions
+}
+
+// Collect every way of using all the remaining tiles in exactly `amt_needed` groups.
+// The lowest remaining tile always starts the next group, but a triplet and a run starting
+// on the same tile can still reach the same groups in another order, so each split is
+// sorted and kept only the first time it is found.
+fn search_groups(
+    counts: &mut [u8; 34],
+    amt_needed: u8,
+    groups: &mut Vec<Group>,
+    found: &mut Vec<Vec<Group>>,
+) {
+    let Some(i) = (0..34).find(|i| counts[*i] > 0) else {
+        if amt_needed == 0 {
+            let mut split = groups.to_owned();
+            split.sort_by_key(|group| (group.tile, group.mentsu as u8));
+            if !found.contains(&split) {
+                found.push(split);
+            }
+        }
+        return;
+    };
+    if amt_needed == 0 {
+        return;
+    }
+    let tile = Pai::from_index(i);
+
+    for (mentsu, size) in [(Mentsu::Kantsu, 4), (Mentsu::Koutsu, 3)] {
+        if counts[i] >= size {
+            counts[i] -= size;
+            groups.push(Group { mentsu, tile });
+            search_groups(counts, amt_needed - 1, groups, found);
+            groups.pop();
+            counts[i] += size;
+        }
+    }
+
+    let could_start_shuntsu = i < 27 && i % 9 < 7 && counts[i + 1] > 0 && counts[i + 2] > 0;
+    if could_start_shuntsu {
+        counts[i] -= 1;
+        counts[i + 1] -= 1;
+        counts[i + 2] -= 1;
+        groups.push(Group {
+            mentsu: Mentsu::Shuntsu,
+            tile,
+        });
+        search_groups(counts, amt_needed - 1, groups, found);
+        groups.pop();
+        counts[i] += 1;
+        counts[i + 1] += 1;
+        counts[i + 2] += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    fn group(mentsu: Mentsu, tilestring: &str) -> Group {
+        Group {
+            mentsu,
+            tile: tilestring.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn incomplete_hand_has_no_decompositions() {
+        let tiles = tilestring_to_pais("147m258p369s12345z").unwrap();
+        assert!(decompose(tiles, 0).is_empty());
+    }
+
+    #[test]
+    fn triple_runs_can_be_read_as_triplets() {
+        let tiles = tilestring_to_pais("111222333m456p11z").unwrap();
+        let decompositions = decompose(tiles, 0);
+        assert_eq!(decompositions.len(), 2);
+        assert!(decompositions.contains(&Decomposition::Regular {
+            groups: vec![
+                group(Mentsu::Koutsu, "1m"),
+                group(Mentsu::Koutsu, "2m"),
+                group(Mentsu::Koutsu, "3m"),
+                group(Mentsu::Shuntsu, "4p"),
+            ],
+            jantou: "1z".parse().unwrap(),
+        }));
+        assert!(decompositions.contains(&Decomposition::Regular {
+            groups: vec![
+                group(Mentsu::Shuntsu, "1m"),
+                group(Mentsu::Shuntsu, "1m"),
+                group(Mentsu::Shuntsu, "1m"),
+                group(Mentsu::Shuntsu, "4p"),
+            ],
+            jantou: "1z".parse().unwrap(),
+        }));
+    }
+
+    #[test]
+    fn triplet_and_run_on_the_same_tile_are_read_once() {
+        let tiles = tilestring_to_pais("333m345m777p999s11z").unwrap();
+        assert_eq!(
+            decompose(tiles, 0),
+            vec![Decomposition::Regular {
+                groups: vec![
+                    group(Mentsu::Koutsu, "3m"),
+                    group(Mentsu::Shuntsu, "3m"),
+                    group(Mentsu::Koutsu, "7p"),
+                    group(Mentsu::Koutsu, "9s"),
+                ],
+                jantou: "1z".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn two_identical_double_runs_are_also_seven_pairs() {
+        let tiles = tilestring_to_pais("112233m445566p77z").unwrap();
+        let decompositions = decompose(tiles, 0);
+        assert_eq!(decompositions.len(), 2);
+        assert!(decompositions.contains(&Decomposition::Chiitoitsu {
+            pairs: tilestring_to_pais("123m456p7z").unwrap(),
+        }));
+    }
+
+    #[test]
+    fn quad_is_two_pairs_only_by_rule() {
+        let tiles = tilestring_to_pais("1111m2299p1122s33z").unwrap();
+        assert!(decompose(tiles.to_owned(), 0).is_empty());
+        let ruleset = Ruleset {
+            chiitoitsu_quads_as_two_pairs: true,
+            ..Ruleset::default()
+        };
+        assert_eq!(
+            decompose_with_ruleset(tiles, 0, &ruleset),
+            vec![Decomposition::Chiitoitsu {
+                pairs: tilestring_to_pais("11m29p12s3z").unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn thirteen_orphans_has_its_own_decomposition() {
+        let tiles = tilestring_to_pais("19m19p19s12345677z").unwrap();
+        assert_eq!(
+            decompose(tiles, 0),
+            vec![Decomposition::KokushiMusou {
+                jantou: "7z".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn melds_reduce_the_groups_needed() {
+        let tiles = tilestring_to_pais("123m11z").unwrap();
+        assert_eq!(
+            decompose(tiles, 3),
+            vec![Decomposition::Regular {
+                groups: vec![group(Mentsu::Shuntsu, "1m")],
+                jantou: "1z".parse().unwrap(),
+            }]
+        );
+        assert!(decompose(tilestring_to_pais("11z").unwrap(), 5).is_empty());
+    }
+}
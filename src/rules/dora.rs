@@ -0,0 +1,151 @@
+/*!
+ * Functions for counting bonus tiles.
+ */
+
+use crate::*;
+
+/**
+ * The amount of each kind of bonus tile in a hand.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DoraCount {
+    /**
+     * Tiles indicated by the dora indicators.
+     */
+    pub dora: u8,
+
+    /**
+     * Tiles indicated by the indicators under the dora indicators.
+     */
+    pub uradora: u8,
+
+    /**
+     * Red fives.
+     */
+    pub akadora: u8,
+}
+
+impl DoraCount {
+    /**
+     * The han added by all of the bonus tiles.
+     */
+    pub fn total(&self) -> u8 {
+        self.dora + self.uradora + self.akadora
+    }
+}
+
+/**
+ * The tile that a dora indicator makes dora.
+ *
+ * This is the next tile in the indicator's suit, or the next wind or dragon, wrapping
+ * around at the end.
+ */
+pub fn dora_from_indicator(indicator: Pai) -> Pai {
+    match indicator {
+        Pai::Suupai(suupai) => Pai::Suupai(Suupai {
+            shoku: suupai.shoku,
+            rank: suupai.rank % 9 + 1,
+            akadora: false,
+        }),
+        Pai::Jihai(Jihai::Kazehai(kazehai)) => Pai::Jihai(Jihai::Kazehai(match kazehai {
+            Kazehai::Ton => Kazehai::Nan,
+            Kazehai::Nan => Kazehai::Shaa,
+            Kazehai::Shaa => Kazehai::Pei,
+            Kazehai::Pei => Kazehai::Ton,
+        })),
+        Pai::Jihai(Jihai::Sangenpai(sangenpai)) => Pai::Jihai(Jihai::Sangenpai(match sangenpai {
+            Sangenpai::Haku => Sangenpai::Hatsu,
+            Sangenpai::Hatsu => Sangenpai::Chun,
+            Sangenpai::Chun => Sangenpai::Haku,
+        })),
+    }
+}
+
+/**
+ * The bonus tiles in a hand.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `melds`: The melds that have been made.
+ * * `indicators`: The dora indicators.
+ * * `ura_indicators`: The uradora indicators, which should be empty unless the winner declared riichi.
+ */
+pub fn count_dora(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    indicators: &[Pai],
+    ura_indicators: &[Pai],
+) -> DoraCount {
+    let tiles: Vec<Pai> = free_tiles
+        .into_iter()
+        .chain(melds.iter().flat_map(|meld| meld.tiles().to_owned()))
+        .collect();
+    let count_indicated = |indicators: &[Pai]| {
+        let amount: usize = indicators
+            .iter()
+            .map(|indicator| {
+                let dora = dora_from_indicator(*indicator);
+                tiles.iter().filter(|t| **t == dora).count()
+            })
+            .sum();
+        u8::try_from(amount).unwrap()
+    };
+    let akadora = tiles.iter().filter(|t| t.is_akadora()).count();
+    DoraCount {
+        dora: count_indicated(indicators),
+        uradora: count_indicated(ura_indicators),
+        akadora: u8::try_from(akadora).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn indicators_point_to_the_next_tile() {
+        assert_eq!(
+            dora_from_indicator("3m".parse().unwrap()),
+            "4m".parse().unwrap()
+        );
+        assert_eq!(
+            dora_from_indicator("9p".parse().unwrap()),
+            "1p".parse().unwrap()
+        );
+        assert_eq!(
+            dora_from_indicator("4z".parse().unwrap()),
+            "1z".parse().unwrap()
+        );
+        assert_eq!(
+            dora_from_indicator(Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun))),
+            Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku))
+        );
+        assert_eq!(
+            dora_from_indicator(Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku))),
+            Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu))
+        );
+    }
+
+    #[test]
+    fn counts_each_kind_of_bonus_tile() {
+        let tiles = tilestring_to_pais("234m406p11s").unwrap();
+        let melds = ["a505s".parse().unwrap()];
+        let result = count_dora(
+            tiles,
+            &melds,
+            &["4s".parse().unwrap(), "9s".parse().unwrap()],
+            &["3p".parse().unwrap()],
+        );
+        assert_eq!(
+            result,
+            DoraCount {
+                dora: 5,
+                uradora: 1,
+                akadora: 2
+            }
+        );
+        assert_eq!(result.total(), 8);
+    }
+}
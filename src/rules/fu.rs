@@ -0,0 +1,294 @@
+/*!
+ * Functions for counting the fu of a complete hand.
+ */
+
+use crate::*;
+
+/**
+ * Something that a winning hand earns fu for.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FuSource {
+    /**
+     * The base fu that every hand gets.
+     */
+    Futei,
+
+    /**
+     * Winning on a discard with a closed hand.
+     */
+    MenzenRon,
+
+    /**
+     * Winning on a self-drawn tile.
+     */
+    Tsumo,
+
+    /**
+     * A set, whether it was made from free tiles or called.
+     */
+    Mentsu {
+        mentsu: Mentsu,
+        tile: Pai,
+        concealed: bool,
+    },
+
+    /**
+     * A pair of yakuhai.
+     */
+    Jantou(Pai),
+
+    /**
+     * A wait on a single tile.
+     */
+    Wait(Wait),
+
+    /**
+     * The fixed fu of seven pairs.
+     */
+    Chiitoitsu,
+
+    /**
+     * The fu that lift an open hand with nothing else to count up to 30 fu.
+     */
+    OpenPinfu,
+}
+
+/**
+ * The fu of a winning hand.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Fu {
+    /**
+     * The fu earned from each source, before rounding.
+     */
+    pub breakdown: Vec<(FuSource, u8)>,
+
+    /**
+     * The fu after rounding up to the next 10, apart from the 25 fu of seven pairs.
+     */
+    pub total: u8,
+}
+
+/**
+ * The fu of a complete hand.
+ *
+ * # Arguments
+ *
+ * * `decomposition`: One reading of the free tiles, including the winning tile.
+ * * `melds`: The melds that have been made.
+ * * `context`: The circumstances of the win.
+ * * `wait`: The shape of the wait that the winning tile completed in this decomposition.
+ */
+pub fn fu(decomposition: &Decomposition, melds: &[Meld], context: &WinContext, wait: Wait) -> Fu {
+    if let Decomposition::Chiitoitsu { .. } = decomposition {
+        return Fu {
+            breakdown: vec![(FuSource::Chiitoitsu, 25)],
+            total: 25,
+        };
+    }
+
+    let menzen = melds.iter().all(Meld::is_concealed);
+    let mut breakdown = vec![(FuSource::Futei, 20)];
+    if menzen && !context.tsumo {
+        breakdown.push((FuSource::MenzenRon, 10));
+    }
+
+    if let Decomposition::Regular { groups, jantou } = decomposition {
+        let free_sets = groups.iter().map(|group| {
+            let completed_by_discard =
+                !context.tsumo && wait == Wait::Shanpon && group.tile == context.winning_tile;
+            (group.mentsu, group.tile, !completed_by_discard)
+        });
+        let called_sets = melds
+            .iter()
+            .map(|meld| (meld.mentsu(), meld.tile(), meld.is_concealed()));
+        for (mentsu, tile, concealed) in free_sets.chain(called_sets) {
+            let base = match mentsu {
+                Mentsu::Shuntsu => continue,
+                Mentsu::Koutsu => 2,
+                Mentsu::Kantsu => 8,
+            };
+            let amount =
+                base * if tile.is_yaochuuhai() { 2 } else { 1 } * if concealed { 2 } else { 1 };
+            breakdown.push((
+                FuSource::Mentsu {
+                    mentsu,
+                    tile,
+                    concealed,
+                },
+                amount,
+            ));
+        }
+
+        let jantou_fu = jantou_fu(*jantou, context);
+        if jantou_fu > 0 {
+            breakdown.push((FuSource::Jantou(*jantou), jantou_fu));
+        }
+    }
+
+    if matches!(wait, Wait::Kanchan | Wait::Penchan | Wait::Tanki) {
+        breakdown.push((FuSource::Wait(wait), 2));
+    }
+
+    let only_futei = breakdown.len() == 1;
+    if context.tsumo && !(menzen && only_futei) {
+        // A closed hand with nothing to count is pinfu, which gets no fu for tsumo.
+        breakdown.push((FuSource::Tsumo, 2));
+    }
+    if !menzen && only_futei && !context.tsumo {
+        breakdown.push((FuSource::OpenPinfu, 10));
+    }
+
+    let sum: u8 = breakdown.iter().map(|(_, amount)| amount).sum();
+    Fu {
+        breakdown,
+        total: sum.div_ceil(10) * 10,
+    }
+}
+
+/**
+ * The fu for a pair of the given tile.
+ */
+fn jantou_fu(tile: Pai, context: &WinContext) -> u8 {
+    match tile {
+        Pai::Jihai(Jihai::Sangenpai(_)) => 2,
+        Pai::Jihai(Jihai::Kazehai(kazehai)) => {
+            2 * (u8::from(kazehai == context.seat_wind) + u8::from(kazehai == context.round_wind))
+        }
+        Pai::Suupai(_) => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    fn first_decomposition(tilestring: &str, amt_melds: u8) -> Decomposition {
+        let tiles = tilestring_to_pais(tilestring).unwrap();
+        decompose(tiles, amt_melds).remove(0)
+    }
+
+    #[test]
+    fn pinfu_tsumo_is_twenty_fu() {
+        let decomposition = first_decomposition("234m567p345678s55p", 0);
+        let context = WinContext::new("2m".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        let result = fu(&decomposition, &[], &context, Wait::Ryanmen);
+        assert_eq!(result.breakdown, vec![(FuSource::Futei, 20)]);
+        assert_eq!(result.total, 20);
+    }
+
+    #[test]
+    fn pinfu_ron_is_thirty_fu() {
+        let decomposition = first_decomposition("234m567p345678s55p", 0);
+        let context = WinContext::new("2m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = fu(&decomposition, &[], &context, Wait::Ryanmen);
+        assert_eq!(
+            result.breakdown,
+            vec![(FuSource::Futei, 20), (FuSource::MenzenRon, 10)]
+        );
+        assert_eq!(result.total, 30);
+    }
+
+    #[test]
+    fn open_pinfu_shape_is_raised_to_thirty_fu() {
+        let decomposition = first_decomposition("234m345678s55p", 1);
+        let melds = ["l456p".parse().unwrap()];
+        let context = WinContext::new("2m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = fu(&decomposition, &melds, &context, Wait::Ryanmen);
+        assert_eq!(
+            result.breakdown,
+            vec![(FuSource::Futei, 20), (FuSource::OpenPinfu, 10)]
+        );
+        assert_eq!(result.total, 30);
+    }
+
+    #[test]
+    fn seven_pairs_is_always_twenty_five_fu() {
+        let tiles = tilestring_to_pais("1199m1199p11s1122z").unwrap();
+        let decomposition = decompose(tiles, 0).remove(0);
+        let context = WinContext::new("2z".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(fu(&decomposition, &[], &context, Wait::Tanki).total, 25);
+    }
+
+    #[test]
+    fn concealed_terminal_triplets_and_a_closed_wait() {
+        let decomposition = first_decomposition("111m999p23455s777z", 0);
+        let context = WinContext::new("3s".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = fu(&decomposition, &[], &context, Wait::Kanchan);
+        assert_eq!(
+            result
+                .breakdown
+                .iter()
+                .map(|(_, amount)| *amount)
+                .collect::<Vec<u8>>(),
+            vec![20, 10, 8, 8, 8, 2]
+        );
+        assert_eq!(result.total, 60);
+    }
+
+    #[test]
+    fn triplet_completed_by_discard_counts_as_open() {
+        let decomposition = first_decomposition("111m999p555s22z", 1);
+        let melds = ["a444z".parse().unwrap()];
+        let context = WinContext::new("5s".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = fu(&decomposition, &melds, &context, Wait::Shanpon);
+        assert!(result.breakdown.contains(&(
+            FuSource::Mentsu {
+                mentsu: Mentsu::Koutsu,
+                tile: "5s".parse().unwrap(),
+                concealed: false,
+            },
+            2
+        )));
+        assert!(result
+            .breakdown
+            .contains(&(FuSource::Jantou("2z".parse().unwrap()), 2)));
+        assert_eq!(result.total, 50);
+    }
+
+    #[test]
+    fn concealed_quad_of_honors_is_thirty_two_fu() {
+        let decomposition = first_decomposition("1111z234m567p789s55p", 0);
+        let context = WinContext::new("5p".parse().unwrap(), true, Kazehai::Nan, Kazehai::Nan);
+        let result = fu(&decomposition, &[], &context, Wait::Tanki);
+        assert!(result.breakdown.contains(&(
+            FuSource::Mentsu {
+                mentsu: Mentsu::Kantsu,
+                tile: "1z".parse().unwrap(),
+                concealed: true,
+            },
+            32
+        )));
+        assert_eq!(result.total, 60);
+    }
+
+    #[test]
+    fn called_and_concealed_quads_count_differently() {
+        let decomposition = first_decomposition("234m567p789s55p", 1);
+        let context = WinContext::new("5p".parse().unwrap(), false, Kazehai::Nan, Kazehai::Nan);
+        let ankan: Meld = "1111z".parse().unwrap();
+        let result = fu(&decomposition, &[ankan], &context, Wait::Tanki);
+        assert_eq!(
+            result.breakdown,
+            vec![
+                (FuSource::Futei, 20),
+                (FuSource::MenzenRon, 10),
+                (
+                    FuSource::Mentsu {
+                        mentsu: Mentsu::Kantsu,
+                        tile: "1z".parse().unwrap(),
+                        concealed: true,
+                    },
+                    32
+                ),
+                (FuSource::Wait(Wait::Tanki), 2),
+            ]
+        );
+        assert_eq!(result.total, 70);
+        let daiminkan: Meld = "a1111z".parse().unwrap();
+        let result = fu(&decomposition, &[daiminkan], &context, Wait::Tanki);
+        assert_eq!(result.total, 40);
+    }
+}
@@ -0,0 +1,493 @@
+/*!
+ * A player's tiles.
+ */
+
+use crate::*;
+
+use core::fmt;
+
+/**
+ * Why a hand cannot have the given tiles, or cannot make the given move.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HandError {
+    /**
+     * The free tiles plus three for each meld are neither 13 nor 14.
+     */
+    WrongTileCount,
+
+    /**
+     * More than four melds.
+     */
+    TooManyMelds,
+
+    /**
+     * More than four copies of a tile.
+     */
+    TooManyCopies(Pai),
+
+    /**
+     * More red fives of a suit than the game has.
+     */
+    TooManyAkadora(Shoku),
+
+    /**
+     * A tile, including whether it is red, that is not among the free tiles.
+     */
+    MissingTile(Pai),
+
+    /**
+     * Drawing or calling a discard when the hand must discard first.
+     */
+    MustDiscard,
+
+    /**
+     * Discarding or calling a quad from the hand when the hand must draw first.
+     */
+    MustDraw,
+
+    /**
+     * An added quad without a pon of the same tile.
+     */
+    MissingPon,
+
+    /**
+     * An added quad whose called tile or source differs from the pon it adds to.
+     */
+    MismatchedPon,
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandError::WrongTileCount => write!(f, "hand must have 13 or 14 tiles"),
+            HandError::TooManyMelds => write!(f, "hand has more than four melds"),
+            HandError::TooManyCopies(pai) => write!(f, "hand has more than four of {pai}"),
+            HandError::TooManyAkadora(shoku) => {
+                write!(f, "hand has too many red fives of {shoku:?}")
+            }
+            HandError::MissingTile(pai) => write!(f, "hand has no {pai}"),
+            HandError::MustDiscard => write!(f, "hand must discard first"),
+            HandError::MustDraw => write!(f, "hand must draw first"),
+            HandError::MissingPon => write!(f, "hand has no pon to add to"),
+            HandError::MismatchedPon => write!(f, "added quad does not match its pon"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+/**
+ * The tiles of a player, split into those that are free and those committed to melds.
+ *
+ * Counting each meld as three tiles, a hand always has 13 tiles while waiting for a draw or
+ * 14 tiles while it must discard.
+ */
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedHand"))]
+pub struct Hand {
+    free_tiles: Vec<Pai>,
+    melds: Vec<Meld>,
+    akadora_limit: [u8; 3],
+}
+
+impl Hand {
+    /**
+     * A hand with the given tiles and melds, in a game with one red five of each suit.
+     */
+    pub fn new(
+        free_tiles: impl IntoIterator<Item = Pai>,
+        melds: &[Meld],
+    ) -> Result<Self, HandError> {
+        Hand::with_akadora_limit(free_tiles, melds, [1, 1, 1])
+    }
+
+    /**
+     * A hand with the given tiles and melds.
+     *
+     * # Arguments
+     *
+     * * `free_tiles`: The tiles that have not been committed to melds.
+     * * `melds`: The melds that have been made.
+     * * `akadora_limit`: How many red fives of manzu, pinzu and souzu respectively the game has.
+     */
+    pub fn with_akadora_limit(
+        free_tiles: impl IntoIterator<Item = Pai>,
+        melds: &[Meld],
+        akadora_limit: [u8; 3],
+    ) -> Result<Self, HandError> {
+        let hand = Hand {
+            free_tiles: free_tiles.into_iter().collect(),
+            melds: melds.to_vec(),
+            akadora_limit,
+        };
+        if hand.melds.len() > 4 {
+            return Err(HandError::TooManyMelds);
+        }
+        if !matches!(hand.amt_tiles(), 13 | 14) {
+            return Err(HandError::WrongTileCount);
+        }
+        hand.check_copies()?;
+        Ok(hand)
+    }
+
+    /**
+     * The tiles that have not been committed to melds.
+     */
+    pub fn free_tiles(&self) -> &[Pai] {
+        &self.free_tiles
+    }
+
+    /**
+     * The melds that have been made.
+     */
+    pub fn melds(&self) -> &[Meld] {
+        &self.melds
+    }
+
+    /**
+     * Whether the hand has drawn or called and must now discard.
+     */
+    pub fn must_discard(&self) -> bool {
+        self.amt_tiles() == 14
+    }
+
+    /**
+     * Whether the hand has 14 tiles that form a complete hand.
+     */
+    pub fn is_complete(&self) -> bool {
+        self.is_complete_with_ruleset(&Ruleset::default())
+    }
+
+    /**
+     * Whether the hand has 14 tiles that form a complete hand under the given rules.
+     */
+    pub fn is_complete_with_ruleset(&self, ruleset: &Ruleset) -> bool {
+        self.must_discard()
+            && is_complete_hand_with_ruleset(self.free_tiles.to_owned(), self.amt_melds(), ruleset)
+    }
+
+    /**
+     * How far the hand is from being ready.
+     */
+    pub fn shanten(&self) -> Shanten {
+        self.shanten_with_ruleset(&Ruleset::default())
+    }
+
+    /**
+     * How far the hand is from being ready under the given rules.
+     */
+    pub fn shanten_with_ruleset(&self, ruleset: &Ruleset) -> Shanten {
+        shanten_with_ruleset(self.free_tiles.to_owned(), self.amt_melds(), ruleset)
+    }
+
+    /**
+     * Adds a drawn tile to the free tiles.
+     */
+    pub fn draw(&mut self, tile: Pai) -> Result<(), HandError> {
+        if self.must_discard() {
+            return Err(HandError::MustDiscard);
+        }
+        self.free_tiles.push(tile);
+        self.check_copies().inspect_err(|_| {
+            self.free_tiles.pop();
+        })
+    }
+
+    /**
+     * Removes a tile from the free tiles.
+     */
+    pub fn discard(&mut self, tile: Pai) -> Result<(), HandError> {
+        if !self.must_discard() {
+            return Err(HandError::MustDraw);
+        }
+        let free_tiles = remove_exact(&self.free_tiles, &[tile])?;
+        self.free_tiles = free_tiles;
+        Ok(())
+    }
+
+    /**
+     * Makes a meld from the free tiles and, unless it is made from the hand alone, the
+     * called tile.
+     *
+     * After a chi or pon the hand must discard, and after a quad it must draw a
+     * replacement tile.
+     */
+    pub fn call(&mut self, meld: Meld) -> Result<(), HandError> {
+        let call = meld.call();
+        let from_hand = matches!(call, Call::Ankan | Call::Shouminkan { .. });
+        if from_hand != self.must_discard() {
+            return Err(if from_hand {
+                HandError::MustDraw
+            } else {
+                HandError::MustDiscard
+            });
+        }
+        // An added quad replaces its pon, so only the other calls make a new meld.
+        let adds_meld = !matches!(call, Call::Shouminkan { .. });
+        if adds_meld && self.melds.len() == 4 {
+            return Err(HandError::TooManyMelds);
+        }
+
+        let mut melds = self.melds.to_owned();
+        let used_tiles = match call {
+            Call::Shouminkan { called, from } => {
+                let pon_index = melds
+                    .iter()
+                    .position(|m| matches!(m.call(), Call::Pon { .. }) && m.tile() == meld.tile())
+                    .ok_or(HandError::MissingPon)?;
+                if melds[pon_index].call() != (Call::Pon { called, from }) {
+                    return Err(HandError::MismatchedPon);
+                }
+                let pon = melds.remove(pon_index);
+                remove_exact(meld.tiles(), pon.tiles()).map_err(|_| HandError::MissingPon)?
+            }
+            Call::Ankan => meld.tiles().to_owned(),
+            Call::Chi { called } | Call::Pon { called, .. } | Call::Daiminkan { called, .. } => {
+                remove_exact(meld.tiles(), &[called])?
+            }
+        };
+        let free_tiles = remove_exact(&self.free_tiles, &used_tiles)?;
+        melds.push(meld);
+        let hand = Hand {
+            free_tiles,
+            melds,
+            akadora_limit: self.akadora_limit,
+        };
+        hand.check_copies()?;
+        *self = hand;
+        Ok(())
+    }
+
+    /**
+     * The amount of tiles, counting each meld as three tiles.
+     */
+    fn amt_tiles(&self) -> usize {
+        self.free_tiles.len() + 3 * self.melds.len()
+    }
+
+    fn amt_melds(&self) -> u8 {
+        u8::try_from(self.melds.len()).unwrap()
+    }
+
+    /**
+     * Checks that no tile or red five has more copies than the game has.
+     */
+    fn check_copies(&self) -> Result<(), HandError> {
+        let tiles: Vec<Pai> = self
+            .free_tiles
+            .iter()
+            .copied()
+            .chain(self.melds.iter().flat_map(|m| m.tiles().to_owned()))
+            .collect();
+        let counts: TileCounts = tiles.iter().copied().collect();
+        if let Some(index) = counts.0.iter().position(|c| *c > 4) {
+            return Err(HandError::TooManyCopies(Pai::from_index(index)));
+        }
+        for (i, shoku) in [Shoku::Manzu, Shoku::Pinzu, Shoku::Souzu]
+            .into_iter()
+            .enumerate()
+        {
+            let amt_akadora = tiles
+                .iter()
+                .filter(|t| matches!(t, Pai::Suupai(s) if s.akadora && s.shoku == shoku))
+                .count();
+            if amt_akadora > usize::from(self.akadora_limit[i]) {
+                return Err(HandError::TooManyAkadora(shoku));
+            }
+        }
+        Ok(())
+    }
+}
+
+/**
+ * A hand as written by serde, before checking its tiles.
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedHand {
+    free_tiles: Vec<Pai>,
+    melds: Vec<Meld>,
+    akadora_limit: [u8; 3],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedHand> for Hand {
+    type Error = HandError;
+
+    fn try_from(hand: UncheckedHand) -> Result<Self, Self::Error> {
+        Hand::with_akadora_limit(hand.free_tiles, &hand.melds, hand.akadora_limit)
+    }
+}
+
+/**
+ * The tiles left after removing each of the given tiles once, matching red fives exactly.
+ */
+fn remove_exact(tiles: &[Pai], to_remove: &[Pai]) -> Result<Vec<Pai>, HandError> {
+    let mut rest = tiles.to_owned();
+    for tile in to_remove {
+        let position = rest
+            .iter()
+            .position(|t| t == tile && t.is_akadora() == tile.is_akadora())
+            .ok_or(HandError::MissingTile(*tile))?;
+        rest.remove(position);
+    }
+    Ok(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::{tilestring_to_hand, tilestring_to_pais};
+
+    fn hand(tilestring: &str) -> Result<Hand, HandError> {
+        let (free_tiles, melds) = tilestring_to_hand(tilestring).unwrap();
+        Hand::new(free_tiles, &melds)
+    }
+
+    #[test]
+    fn tile_count_must_match_the_melds() {
+        assert!(hand("123456789m1234p").is_ok());
+        assert!(hand("123456789m12345p").is_ok());
+        assert_eq!(hand("123456789m123p"), Err(HandError::WrongTileCount));
+        assert!(hand("456789m1234p[l123m]").is_ok());
+        assert_eq!(
+            hand("123456789m1234p[l123m]"),
+            Err(HandError::WrongTileCount)
+        );
+    }
+
+    #[test]
+    fn copies_are_limited() {
+        let tiles = ["1m".parse().unwrap(); 5]
+            .into_iter()
+            .chain(tilestring_to_pais("23456789p").unwrap());
+        let result = Hand::new(tiles, &[]);
+        assert_eq!(result, Err(HandError::TooManyCopies("1m".parse().unwrap())));
+        assert_eq!(
+            hand("12345m0p99s11z[a055p]"),
+            Err(HandError::TooManyAkadora(Shoku::Pinzu))
+        );
+        let (free_tiles, melds) = tilestring_to_hand("12345m0p99s11z[a055p]").unwrap();
+        assert!(Hand::with_akadora_limit(free_tiles, &melds, [1, 2, 1]).is_ok());
+    }
+
+    #[test]
+    fn draws_and_discards_alternate() {
+        let mut hand = hand("123456789m1234p").unwrap();
+        assert_eq!(
+            hand.discard("1m".parse().unwrap()),
+            Err(HandError::MustDraw)
+        );
+        hand.draw("4p".parse().unwrap()).unwrap();
+        assert!(hand.must_discard());
+        assert!(hand.is_complete());
+        assert_eq!(
+            hand.draw("5p".parse().unwrap()),
+            Err(HandError::MustDiscard)
+        );
+        assert_eq!(
+            hand.discard("9s".parse().unwrap()),
+            Err(HandError::MissingTile("9s".parse().unwrap()))
+        );
+        hand.discard("1m".parse().unwrap()).unwrap();
+        assert_eq!(hand.free_tiles().len(), 13);
+    }
+
+    #[test]
+    fn red_fives_are_discarded_exactly() {
+        let mut hand = hand("123456789m1230p").unwrap();
+        hand.draw("9s".parse().unwrap()).unwrap();
+        assert_eq!(
+            hand.discard("5p".parse().unwrap()),
+            Err(HandError::MissingTile("5p".parse().unwrap()))
+        );
+        hand.discard("0p".parse().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn calls_take_tiles_from_the_hand() {
+        let mut hand = hand("123456789m1122p").unwrap();
+        let pon: Meld = "a222p".parse().unwrap();
+        hand.call(pon).unwrap();
+        assert!(hand.must_discard());
+        assert_eq!(hand.free_tiles().len(), 11);
+        hand.discard("1p".parse().unwrap()).unwrap();
+        hand.draw("2p".parse().unwrap()).unwrap();
+        hand.call("a+2222p".parse().unwrap()).unwrap();
+        assert_eq!(hand.melds().len(), 1);
+        assert!(matches!(hand.melds()[0].call(), Call::Shouminkan { .. }));
+        assert!(!hand.must_discard());
+        hand.draw("1p".parse().unwrap()).unwrap();
+        assert!(hand.is_complete());
+    }
+
+    #[test]
+    fn added_quad_keeps_the_source_of_its_pon() {
+        let mut hand = hand("123456789m1122p").unwrap();
+        hand.call("a222p".parse().unwrap()).unwrap();
+        hand.discard("1p".parse().unwrap()).unwrap();
+        hand.draw("2p".parse().unwrap()).unwrap();
+        assert_eq!(
+            hand.call("r+2222p".parse().unwrap()),
+            Err(HandError::MismatchedPon)
+        );
+        assert!(matches!(hand.melds()[0].call(), Call::Pon { .. }));
+        assert!(hand.call("a+2222p".parse().unwrap()).is_ok());
+    }
+
+    #[test]
+    fn added_quad_is_allowed_with_four_melds() {
+        let mut hand = hand("1s[l123m][l456m][l789m][a222p]").unwrap();
+        hand.draw("2p".parse().unwrap()).unwrap();
+        hand.call("a+2222p".parse().unwrap()).unwrap();
+        assert_eq!(hand.melds().len(), 4);
+        hand.draw("1s".parse().unwrap()).unwrap();
+        assert!(hand.is_complete());
+        hand.discard("1s".parse().unwrap()).unwrap();
+        assert_eq!(
+            hand.call("a111s".parse().unwrap()),
+            Err(HandError::TooManyMelds)
+        );
+    }
+
+    #[test]
+    fn quads_are_two_pairs_only_by_rule() {
+        let hand = hand("1111m2299p1122s33z").unwrap();
+        let ruleset = Ruleset {
+            chiitoitsu_quads_as_two_pairs: true,
+            ..Ruleset::default()
+        };
+        assert!(!hand.is_complete());
+        assert!(hand.is_complete_with_ruleset(&ruleset));
+        assert_eq!(hand.shanten().minimum, 1);
+        assert_eq!(hand.shanten_with_ruleset(&ruleset).minimum, -1);
+    }
+
+    #[test]
+    fn calls_need_the_right_tiles_and_timing() {
+        let mut hand = hand("113456789m1222p").unwrap();
+        assert_eq!(
+            hand.call("l789p".parse().unwrap()),
+            Err(HandError::MissingTile("8p".parse().unwrap()))
+        );
+        assert_eq!(
+            hand.call("2222p".parse().unwrap()),
+            Err(HandError::MustDraw)
+        );
+        assert_eq!(
+            hand.call("a+1111m".parse().unwrap()),
+            Err(HandError::MustDraw)
+        );
+        hand.draw("2p".parse().unwrap()).unwrap();
+        assert_eq!(
+            hand.call("a+2222p".parse().unwrap()),
+            Err(HandError::MissingPon)
+        );
+        hand.call("2222p".parse().unwrap()).unwrap();
+        assert_eq!(hand.free_tiles().len(), 10);
+        assert!(!hand.must_discard());
+    }
+}
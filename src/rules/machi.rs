@@ -0,0 +1,286 @@
+/*!
+ * Functions for finding the tiles that a hand is waiting on.
+ */
+
+use crate::*;
+
+/**
+ * The shape of a wait, by the role of the winning tile.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Wait {
+    /**
+     * Either end of two consecutive tiles, e.g. 23 waiting on 1 or 4.
+     */
+    Ryanmen,
+
+    /**
+     * The middle of a run, e.g. 13 waiting on 2.
+     */
+    Kanchan,
+
+    /**
+     * The inside of a terminal pair of tiles, e.g. 12 waiting on 3.
+     */
+    Penchan,
+
+    /**
+     * One of two pairs, e.g. 11 and 99 waiting on 1 or 9.
+     */
+    Shanpon,
+
+    /**
+     * The second tile of the pair.
+     */
+    Tanki,
+}
+
+/**
+ * The tiles that would complete a hand.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Machi {
+    /**
+     * Winning tiles of which at least one copy has not been used by the hand.
+     */
+    pub live: Vec<Pai>,
+
+    /**
+     * Winning tiles of which all four copies are already held by the hand.
+     */
+    pub karaten: Vec<Pai>,
+}
+
+impl Machi {
+    /**
+     * Whether any tile would complete the hand, even if it cannot be drawn.
+     */
+    pub fn is_tenpai(&self) -> bool {
+        !self.live.is_empty() || !self.karaten.is_empty()
+    }
+}
+
+/**
+ * The tiles that would complete a hand.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `melds`: The melds that have been made so far.
+ */
+pub fn machi(free_tiles: impl IntoIterator<Item = Pai>, melds: &[Meld]) -> Machi {
+    machi_with_ruleset(free_tiles, melds, &Ruleset::default())
+}
+
+/**
+ * The tiles that would complete a hand under the given rules.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `melds`: The melds that have been made so far.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn machi_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    ruleset: &Ruleset,
+) -> Machi {
+    let free_counts: TileCounts = free_tiles.into_iter().collect();
+    let amt_melds = u8::try_from(melds.len()).unwrap();
+    let mut held = free_counts;
+    for tile in melds.iter().flat_map(|meld| meld.tiles().to_owned()) {
+        held.add(tile);
+    }
+
+    let mut result = Machi::default();
+    for tile in all_pais() {
+        let mut candidate = free_counts;
+        candidate.add(tile);
+        if !is_complete_counts_with_ruleset(&candidate, amt_melds, ruleset) {
+            continue;
+        }
+        if held[tile] >= 4 {
+            result.karaten.push(tile);
+        } else {
+            result.live.push(tile);
+        }
+    }
+    result
+}
+
+/**
+ * The possible shapes of the wait completed by the winning tile in one reading of a hand.
+ *
+ * The result has more than one shape when the winning tile could have completed more than
+ * one of the groups.
+ */
+pub fn wait_shapes(decomposition: &Decomposition, winning_tile: Pai) -> Vec<Wait> {
+    let mut shapes = vec![];
+    match decomposition {
+        Decomposition::Regular { groups, jantou } => {
+            for group in groups {
+                if let Some(shape) = group_wait_shape(group, winning_tile) {
+                    shapes.push(shape);
+                }
+            }
+            if *jantou == winning_tile {
+                shapes.push(Wait::Tanki);
+            }
+        }
+        Decomposition::Chiitoitsu { pairs } => {
+            if pairs.contains(&winning_tile) {
+                shapes.push(Wait::Tanki);
+            }
+        }
+        Decomposition::KokushiMusou { .. } => shapes.push(Wait::Tanki),
+    }
+    let mut unique = vec![];
+    for shape in shapes {
+        if !unique.contains(&shape) {
+            unique.push(shape);
+        }
+    }
+    unique
+}
+
+/**
+ * Every reading of a complete hand along with the possible shapes of its wait.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds, including the winning tile.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ * * `winning_tile`: The tile that completed the hand.
+ */
+pub fn classify_waits(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    amt_melds: u8,
+    winning_tile: Pai,
+) -> Vec<(Decomposition, Vec<Wait>)> {
+    decompose(free_tiles, amt_melds)
+        .into_iter()
+        .map(|decomposition| {
+            let shapes = wait_shapes(&decomposition, winning_tile);
+            (decomposition, shapes)
+        })
+        .collect()
+}
+
+/**
+ * The shape of the wait if the winning tile completed the given group.
+ */
+fn group_wait_shape(group: &Group, winning_tile: Pai) -> Option<Wait> {
+    match (group.mentsu, group.tile, winning_tile) {
+        (Mentsu::Koutsu, tile, winner) if tile == winner => Some(Wait::Shanpon),
+        (Mentsu::Shuntsu, Pai::Suupai(start), Pai::Suupai(winner))
+            if start.shoku == winner.shoku =>
+        {
+            match winner.rank.checked_sub(start.rank) {
+                Some(0) if start.rank == 7 => Some(Wait::Penchan),
+                Some(0) => Some(Wait::Ryanmen),
+                Some(1) => Some(Wait::Kanchan),
+                Some(2) if start.rank == 1 => Some(Wait::Penchan),
+                Some(2) => Some(Wait::Ryanmen),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn seven_pairs_can_wait_on_a_fourth_copy_by_rule() {
+        let tiles = tilestring_to_pais("111m2299p1122s33z").unwrap();
+        assert!(!machi(tiles.to_owned(), &[]).is_tenpai());
+        let ruleset = Ruleset {
+            chiitoitsu_quads_as_two_pairs: true,
+            ..Ruleset::default()
+        };
+        let result = machi_with_ruleset(tiles.to_owned(), &[], &ruleset);
+        assert_eq!(result.live, vec![tiles[0]]);
+        assert!(result.karaten.is_empty());
+    }
+
+    #[test]
+    fn open_wait_has_two_tiles() {
+        let tiles = tilestring_to_pais("23m456p789s11122z").unwrap();
+        let result = machi(tiles, &[]);
+        assert_eq!(result.live, tilestring_to_pais("14m").unwrap());
+        assert!(result.karaten.is_empty());
+    }
+
+    #[test]
+    fn pair_wait_has_one_tile() {
+        let tiles = tilestring_to_pais("123m456p789s1112z").unwrap();
+        let result = machi(tiles, &[]);
+        assert_eq!(result.live, tilestring_to_pais("2z").unwrap());
+    }
+
+    #[test]
+    fn nine_gates_waits_on_every_tile_of_the_suit() {
+        let tiles = tilestring_to_pais("1112345678999m").unwrap();
+        let result = machi(tiles, &[]);
+        assert_eq!(result.live, tilestring_to_pais("123456789m").unwrap());
+    }
+
+    #[test]
+    fn waiting_on_a_tile_held_four_times_is_karaten() {
+        let tiles = tilestring_to_pais("5z234p567p789s").unwrap();
+        let melds = ["a555z".parse().unwrap()];
+        let result = machi(tiles, &melds);
+        assert!(result.live.is_empty());
+        assert_eq!(result.karaten, tilestring_to_pais("5z").unwrap());
+        assert!(result.is_tenpai());
+    }
+
+    #[test]
+    fn runs_are_classified_by_the_position_of_the_winning_tile() {
+        let shapes = |tilestring: &str, winner: &str| {
+            let winning_tile = winner.parse().unwrap();
+            classify_waits(tilestring_to_pais(tilestring).unwrap(), 3, winning_tile)
+                .into_iter()
+                .flat_map(|(_, shapes)| shapes)
+                .collect::<Vec<Wait>>()
+        };
+        assert_eq!(shapes("234m11z", "2m"), vec![Wait::Ryanmen]);
+        assert_eq!(shapes("234m11z", "3m"), vec![Wait::Kanchan]);
+        assert_eq!(shapes("123m11z", "3m"), vec![Wait::Penchan]);
+        assert_eq!(shapes("789m11z", "7m"), vec![Wait::Penchan]);
+        assert_eq!(shapes("789m11z", "9m"), vec![Wait::Ryanmen]);
+        assert_eq!(shapes("789m11z", "1z"), vec![Wait::Tanki]);
+    }
+
+    #[test]
+    fn winning_tile_can_play_several_roles() {
+        let tiles = tilestring_to_pais("11123m456p789s111z").unwrap();
+        let winning_tile = "1m".parse().unwrap();
+        let result = classify_waits(tiles, 0, winning_tile);
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].1, vec![Wait::Ryanmen, Wait::Tanki]);
+    }
+
+    #[test]
+    fn each_decomposition_has_its_own_shapes() {
+        let tiles = tilestring_to_pais("111222333m456p11z").unwrap();
+        let winning_tile = "3m".parse().unwrap();
+        let shapes: Vec<Vec<Wait>> = classify_waits(tiles, 0, winning_tile)
+            .into_iter()
+            .map(|(_, shapes)| shapes)
+            .collect();
+        assert_eq!(shapes.len(), 2);
+        assert!(shapes.contains(&vec![Wait::Shanpon]));
+        assert!(shapes.contains(&vec![Wait::Penchan]));
+    }
+
+    #[test]
+    fn scattered_tiles_wait_on_nothing() {
+        let tiles = tilestring_to_pais("147m258p369s1234z").unwrap();
+        assert!(!machi(tiles, &[]).is_tenpai());
+    }
+}
@@ -2,41 +2,240 @@
  * Functions for checking whether a collection of pais is a mentsu.
  */
 
-// At time of writing, this lint suggests an incorrect refactoring.
-#![allow(clippy::unnecessary_to_owned)]
-
 use crate::*;
 
-use itertools::Itertools;
+use core::fmt;
 
 /**
  * A tile group.
  */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Mentsu {
     Kantsu,
     Koutsu,
     Shuntsu,
 }
 
+/**
+ * A player relative to the one who made a call.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RelativeSeat {
+    /**
+     * The player to the left, who discards just before.
+     */
+    Kamicha,
+
+    /**
+     * The player across.
+     */
+    Toimen,
+
+    /**
+     * The player to the right, who discards just after.
+     */
+    Shimocha,
+}
+
+/**
+ * How a meld was made.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Call {
+    /**
+     * A run completed with a discard, which is always from the kamicha.
+     */
+    Chi { called: Pai },
+
+    /**
+     * A triplet completed with a discard.
+     */
+    Pon { called: Pai, from: RelativeSeat },
+
+    /**
+     * A quad completed with a discard.
+     */
+    Daiminkan { called: Pai, from: RelativeSeat },
+
+    /**
+     * A quad made from the player's own tiles, which keeps the hand closed.
+     */
+    Ankan,
+
+    /**
+     * A quad made by adding a tile to a pon, keeping the called tile and seat of the pon.
+     */
+    Shouminkan { called: Pai, from: RelativeSeat },
+}
+
+impl Call {
+    /**
+     * The kind of group that the call makes.
+     */
+    pub fn mentsu(&self) -> Mentsu {
+        match self {
+            Call::Chi { .. } => Mentsu::Shuntsu,
+            Call::Pon { .. } => Mentsu::Koutsu,
+            Call::Daiminkan { .. } | Call::Ankan | Call::Shouminkan { .. } => Mentsu::Kantsu,
+        }
+    }
+
+    /**
+     * The discarded tile that was called, if any.
+     */
+    pub fn called_tile(&self) -> Option<Pai> {
+        match self {
+            Call::Chi { called }
+            | Call::Pon { called, .. }
+            | Call::Daiminkan { called, .. }
+            | Call::Shouminkan { called, .. } => Some(*called),
+            Call::Ankan => None,
+        }
+    }
+
+    /**
+     * The player whose discard was called, if any.
+     */
+    pub fn from(&self) -> Option<RelativeSeat> {
+        match self {
+            Call::Chi { .. } => Some(RelativeSeat::Kamicha),
+            Call::Pon { from, .. }
+            | Call::Daiminkan { from, .. }
+            | Call::Shouminkan { from, .. } => Some(*from),
+            Call::Ankan => None,
+        }
+    }
+}
+
+/**
+ * Why tiles cannot make a meld.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeldError {
+    /**
+     * The tiles do not form the kind of group that the call makes.
+     */
+    WrongMentsu,
+
+    /**
+     * The called tile, including whether it is red, is not among the tiles.
+     */
+    MissingCalledTile,
+}
+
+impl fmt::Display for MeldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeldError::WrongMentsu => write!(f, "tiles do not form the group made by the call"),
+            MeldError::MissingCalledTile => write!(f, "called tile is not among the tiles"),
+        }
+    }
+}
+
+impl std::error::Error for MeldError {}
+
 /**
  * A group whose tiles have been committed by a call.
  */
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedMeld"))]
 pub struct Meld {
+    call: Call,
+    tiles: Vec<Pai>,
+}
+
+impl Meld {
+    /**
+     * A meld made by the given call, if the tiles form the group that it makes.
+     *
+     * # Arguments
+     *
+     * * `call`: How the meld was made.
+     * * `tiles`: Every tile of the meld, including the called tile.
+     */
+    pub fn new(call: Call, tiles: impl IntoIterator<Item = Pai>) -> Result<Meld, MeldError> {
+        let mut tiles: Vec<Pai> = tiles.into_iter().collect();
+        let forms_mentsu = match call.mentsu() {
+            Mentsu::Kantsu => is_kantsu(tiles.to_owned()),
+            Mentsu::Koutsu => is_koutsu(tiles.to_owned()),
+            Mentsu::Shuntsu => is_shuntsu(tiles.to_owned()),
+        };
+        if !forms_mentsu {
+            return Err(MeldError::WrongMentsu);
+        }
+        if let Some(called) = call.called_tile() {
+            if !tiles
+                .iter()
+                .any(|t| *t == called && t.is_akadora() == called.is_akadora())
+            {
+                return Err(MeldError::MissingCalledTile);
+            }
+        }
+        sort_pais(&mut tiles);
+        Ok(Meld { call, tiles })
+    }
+
     /**
-     * The type of meld.
+     * How the meld was made.
      */
-    pub mentsu: Mentsu,
+    pub fn call(&self) -> Call {
+        self.call
+    }
+
+    /**
+     * The kind of group that the meld is.
+     */
+    pub fn mentsu(&self) -> Mentsu {
+        self.call.mentsu()
+    }
 
     /**
      * For sets, this is the tile that is represented.
      * For sequences, this is the lowest rank tile that is represented.
+     *
+     * This is never a red five.
      */
-    pub tile: Pai,
+    pub fn tile(&self) -> Pai {
+        Pai::from_index(self.tiles[0].index())
+    }
 
     /**
-     * Whether any of the represented tiles is a red dora.
+     * The tiles that make up the meld, sorted, with red fives before other fives.
      */
-    pub akadora: bool,
+    pub fn tiles(&self) -> &[Pai] {
+        &self.tiles
+    }
+
+    /**
+     * Whether the meld keeps the hand closed, which is only true of a concealed quad.
+     */
+    pub fn is_concealed(&self) -> bool {
+        self.call == Call::Ankan
+    }
+}
+
+/**
+ * A meld as written by serde, before checking its tiles.
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedMeld {
+    call: Call,
+    tiles: Vec<Pai>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedMeld> for Meld {
+    type Error = MeldError;
+
+    fn try_from(meld: UncheckedMeld) -> Result<Self, Self::Error> {
+        Meld::new(meld.call, meld.tiles)
+    }
 }
 
 /**
@@ -112,113 +311,28 @@ pub fn is_shuntsu(tiles: impl IntoIterator<Item = Pai>) -> bool {
     }
 }
 
-/**
- * Amount of times that the given tile repeats in the collection of tiles.
- */
-fn count_repeats_of_pai(tile: Pai, ts: impl IntoIterator<Item = Pai>) -> usize {
-    ts.into_iter().filter(|t| *t == tile).count()
-}
-
-fn possible_pair_pais(ts: impl IntoIterator<Item = Pai>) -> Vec<Pai> {
-    let ts_vec: Vec<Pai> = ts.into_iter().collect();
-    let unduped_tiles = ts_vec
-        .iter()
-        .filter(|t| count_repeats_of_pai(**t, ts_vec.to_owned()) == 2);
-    let unique_tiles = unduped_tiles.unique();
-    let unique_vec: Vec<Pai> = unique_tiles.copied().collect();
-    unique_vec
-}
-
 /**
  * Whether a hand is seven pairs.
  */
-fn is_chiitoitsu(tiles: impl IntoIterator<Item = Pai>) -> bool {
-    // TODO: Option to allow quads as two pairs.
-    let tiles_vec: Vec<Pai> = tiles.into_iter().collect();
-    if tiles_vec.len() != 14 {
-        return false;
-    }
-    if possible_pair_pais(tiles_vec).len() != 7 {
-        return false;
+fn is_chiitoitsu(counts: &TileCounts, ruleset: &Ruleset) -> bool {
+    if ruleset.chiitoitsu_quads_as_two_pairs {
+        counts.total() == 14 && counts.0.iter().all(|c| c % 2 == 0)
+    } else {
+        counts.total() == 14 && counts.0.iter().filter(|c| **c == 2).count() == 7
     }
-    true
 }
 
 /**
  * Whether a hand is thirteen orphans.
  */
-fn is_kokushi_musou(tiles: impl IntoIterator<Item = Pai>) -> bool {
-    let tiles_vec: Vec<Pai> = tiles.into_iter().collect();
-    if tiles_vec.len() != 14 {
-        return false;
-    }
-    if possible_pair_pais(tiles_vec.to_owned()).len() != 1 {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Kazehai(Kazehai::Ton))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Kazehai(Kazehai::Nan))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Kazehai(Kazehai::Shaa))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Kazehai(Kazehai::Pei))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu))) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Manzu,
-        rank: 1,
-        akadora: false,
-    })) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Manzu,
-        rank: 9,
-        akadora: false,
-    })) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Pinzu,
-        rank: 1,
-        akadora: false,
-    })) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Pinzu,
-        rank: 9,
-        akadora: false,
-    })) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Souzu,
-        rank: 1,
-        akadora: false,
-    })) {
-        return false;
-    }
-    if !tiles_vec.contains(&Pai::Suupai(Suupai {
-        shoku: Shoku::Souzu,
-        rank: 9,
-        akadora: false,
-    })) {
-        return false;
-    }
-    true
+fn is_kokushi_musou(counts: &TileCounts) -> bool {
+    counts.total() == 14
+        && YAOCHUUHAI_INDICES.iter().all(|i| counts.0[*i] >= 1)
+        && YAOCHUUHAI_INDICES
+            .iter()
+            .filter(|i| counts.0[**i] == 2)
+            .count()
+            == 1
 }
 
 /**
@@ -230,236 +344,114 @@ fn is_kokushi_musou(tiles: impl IntoIterator<Item = Pai>) -> bool {
  * * `amt_melds`: The amount of melds that have been made so far.
  */
 pub fn is_complete_hand(free_tiles: impl IntoIterator<Item = Pai>, amt_melds: u8) -> bool {
-    fn could_start_shunstu(tile: Pai, ts: impl IntoIterator<Item = Pai>) -> bool {
-        match tile {
-            Pai::Jihai(_) => false,
-            Pai::Suupai(suupai) => {
-                if suupai.rank > 7 {
-                    return false;
-                }
-                let ts_vec: Vec<Pai> = ts.into_iter().collect();
-                let mid = ts_vec.iter().find(|t| {
-                    if let Pai::Suupai(s) = t {
-                        s.shoku == suupai.shoku && s.rank == suupai.rank + 1
-                    } else {
-                        false
-                    }
-                });
-                let high = ts_vec.iter().find(|t| {
-                    if let Pai::Suupai(s) = t {
-                        s.shoku == suupai.shoku && s.rank == suupai.rank + 2
-                    } else {
-                        false
-                    }
-                });
-                mid.is_some() && high.is_some()
-            }
-        }
-    }
+    is_complete_hand_with_ruleset(free_tiles, amt_melds, &Ruleset::default())
+}
 
-    fn remove_shuntsu(start: Pai, ts: impl IntoIterator<Item = Pai>) -> Vec<Pai> {
-        if let Pai::Suupai(suupai) = start {
-            let mut ts_vec: Vec<Pai> = ts.into_iter().collect();
-            let low_index = ts_vec.iter().copied().position(|t| t == start);
-            ts_vec.remove(low_index.unwrap());
-            let mid_index = ts_vec.to_owned().into_iter().position(|t| {
-                t == Pai::Suupai(Suupai {
-                    shoku: suupai.shoku,
-                    rank: suupai.rank + 1,
-                    akadora: false,
-                })
-            });
-            ts_vec.remove(mid_index.unwrap());
-            let high_index = ts_vec.to_owned().into_iter().position(|t| {
-                t == Pai::Suupai(Suupai {
-                    shoku: suupai.shoku,
-                    rank: suupai.rank + 2,
-                    akadora: false,
-                })
-            });
-            ts_vec.remove(high_index.unwrap());
-            ts_vec
-        } else {
-            ts.into_iter().collect()
-        }
-    }
+/**
+ * Whether a collection of tiles is a complete hand under the given rules.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn is_complete_hand_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    amt_melds: u8,
+    ruleset: &Ruleset,
+) -> bool {
+    is_complete_counts_with_ruleset(&free_tiles.into_iter().collect(), amt_melds, ruleset)
+}
 
-    fn possible_shuntsu_starts(ts: impl IntoIterator<Item = Pai>) -> Vec<Pai> {
-        let ts_vec: Vec<Pai> = ts.into_iter().collect();
-        let starts = ts_vec
-            .iter()
-            .filter(|t| could_start_shunstu(**t, ts_vec.to_owned()));
-        let starts_unique = starts.unique();
-        let starts_vec: Vec<Pai> = starts_unique.copied().collect();
-        starts_vec
-    }
+/**
+ * Whether the counted tiles are a complete hand.
+ *
+ * Four copies of a tile among the free tiles may be used as a quad.
+ *
+ * # Arguments
+ *
+ * * `counts`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ */
+pub fn is_complete_counts(counts: &TileCounts, amt_melds: u8) -> bool {
+    is_complete_counts_with_ruleset(counts, amt_melds, &Ruleset::default())
+}
 
-    fn possible_quad_pais(ts: impl IntoIterator<Item = Pai>) -> Vec<Pai> {
-        let ts_vec: Vec<Pai> = ts.into_iter().collect();
-        let unduped_tiles = ts_vec
-            .iter()
-            .filter(|t| count_repeats_of_pai(**t, ts_vec.to_owned()) == 4);
-        let unique_tiles = unduped_tiles.unique();
-        let unique_vec: Vec<Pai> = unique_tiles.copied().collect();
-        unique_vec
+/**
+ * Whether the counted tiles are a complete hand under the given rules.
+ *
+ * Four copies of a tile among the free tiles may be used as a quad.
+ *
+ * # Arguments
+ *
+ * * `counts`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn is_complete_counts_with_ruleset(
+    counts: &TileCounts,
+    amt_melds: u8,
+    ruleset: &Ruleset,
+) -> bool {
+    if amt_melds == 0 && (is_chiitoitsu(counts, ruleset) || is_kokushi_musou(counts)) {
+        return true;
     }
-
-    fn possible_trip_pais(ts: impl IntoIterator<Item = Pai>) -> Vec<Pai> {
-        let ts_vec: Vec<Pai> = ts.into_iter().collect();
-        let unduped_tiles = ts_vec
-            .iter()
-            .filter(|t| count_repeats_of_pai(**t, ts_vec.to_owned()) == 3);
-        let unique_tiles = unduped_tiles.unique();
-        let unique_vec: Vec<Pai> = unique_tiles.copied().collect();
-        unique_vec
-    }
-
-    // Whether the remaining pais form a complete hand with the pais put aside.
-    fn remaining_pais_complete_hand(
-        ts: impl IntoIterator<Item = Pai>,
-        possible_quad_tiles: impl IntoIterator<Item = Pai> + Clone,
-        possible_trip_tiles: impl IntoIterator<Item = Pai> + Clone,
-        possible_shuntsu_tiles: impl IntoIterator<Item = Pai> + Clone,
-        amt_melds: u8,
-    ) -> bool {
-        let ts_vec: Vec<Pai> = ts.into_iter().collect();
-
-        // For each quad candidate, try including and omitting it.
-        let quad_tiles: Vec<Pai> = possible_quad_tiles.to_owned().into_iter().collect();
-        if let Some((head, tail)) = quad_tiles.split_first() {
-            let tiles_left_in_hand: Vec<Pai> = ts_vec
-                .to_owned()
-                .into_iter()
-                .filter(|t| *t != *head)
-                .collect();
-            let remaining_quad_candidates = tail.to_vec();
-            if remaining_pais_complete_hand(
-                tiles_left_in_hand,
-                remaining_quad_candidates.to_owned(),
-                possible_trip_tiles
-                    .to_owned()
-                    .into_iter()
-                    .filter(|t| *t != *head)
-                    .collect_vec(),
-                possible_shuntsu_tiles
-                    .to_owned()
-                    .into_iter()
-                    .filter(|t| *t != *head)
-                    .collect_vec(),
-                amt_melds + 1,
-            ) {
-                return true;
-            }
-            if remaining_pais_complete_hand(
-                ts_vec.to_owned(),
-                remaining_quad_candidates,
-                possible_trip_tiles.to_owned(),
-                possible_shuntsu_tiles.to_owned(),
-                amt_melds,
-            ) {
-                return true;
-            }
+    if amt_melds > 4 {
+        return false;
+    }
+    let mut remaining = counts.0;
+    (0..34).any(|jantou_index| {
+        if remaining[jantou_index] < 2 {
+            return false;
         }
+        remaining[jantou_index] -= 2;
+        let complete = can_form_groups(&mut remaining, 0, 4 - amt_melds);
+        remaining[jantou_index] += 2;
+        complete
+    })
+}
 
-        // For each trip candidate, try including and omitting it.
-        let trip_tiles: Vec<Pai> = possible_trip_tiles.to_owned().into_iter().collect();
-        if let Some((head, tail)) = trip_tiles.split_first() {
-            let tiles_left_in_hand: Vec<Pai> = ts_vec
-                .to_owned()
-                .into_iter()
-                .filter(|t| *t != *head)
-                .collect();
-            let remaining_trip_candidates = tail.to_vec();
-            if remaining_pais_complete_hand(
-                tiles_left_in_hand.to_owned(),
-                possible_quad_tiles
-                    .to_owned()
-                    .into_iter()
-                    .filter(|t| *t != *head)
-                    .collect_vec(),
-                remaining_trip_candidates.to_owned(),
-                possible_shuntsu_starts(tiles_left_in_hand),
-                amt_melds + 1,
-            ) {
-                return true;
-            }
-            if remaining_pais_complete_hand(
-                ts_vec.to_owned(),
-                possible_quad_tiles.to_owned(),
-                remaining_trip_candidates,
-                possible_shuntsu_tiles.to_owned(),
-                amt_melds,
-            ) {
-                return true;
-            }
-        }
+// Whether all the remaining tiles can be used in exactly `amt_needed` groups.
+// Every tile before `start` has already been used.
+fn can_form_groups(counts: &mut [u8; 34], start: usize, amt_needed: u8) -> bool {
+    let Some(i) = (start..34).find(|i| counts[*i] > 0) else {
+        return amt_needed == 0;
+    };
+    if amt_needed == 0 {
+        return false;
+    }
 
-        // For each shuntsu candidate, try including and omitting it.
-        let shuntsu_starts: Vec<Pai> = possible_shuntsu_tiles.into_iter().collect();
-        if let Some((head, tail)) = shuntsu_starts.split_first() {
-            let tiles_left_in_hand: Vec<Pai> = remove_shuntsu(*head, ts_vec.to_owned());
-            let remaining_shuntsu_candidates: Vec<Pai> = tail
-                .iter()
-                .filter(|t| could_start_shunstu(**t, tiles_left_in_hand.to_owned()))
-                .copied()
-                .collect();
-            let first_case_candidates: Vec<Pai> =
-                if could_start_shunstu(*head, tiles_left_in_hand.to_owned()) {
-                    // If the tile can still start another run then reconsider it in the first case.
-                    let mut copy = remaining_shuntsu_candidates.to_owned();
-                    copy.push(*head);
-                    copy
-                } else {
-                    remaining_shuntsu_candidates.to_owned()
-                };
-            if remaining_pais_complete_hand(
-                tiles_left_in_hand.to_owned(),
-                possible_quad_tiles
-                    .to_owned()
-                    .into_iter()
-                    .filter(|t| tiles_left_in_hand.contains(t))
-                    .collect_vec(),
-                possible_trip_tiles
-                    .to_owned()
-                    .into_iter()
-                    .filter(|t| tiles_left_in_hand.contains(t))
-                    .collect_vec(),
-                first_case_candidates,
-                amt_melds + 1,
-            ) {
-                return true;
-            }
-            if remaining_pais_complete_hand(
-                ts_vec.to_owned(),
-                possible_quad_tiles,
-                possible_trip_tiles,
-                remaining_shuntsu_candidates,
-                amt_melds,
-            ) {
+    for size in [4, 3] {
+        if counts[i] >= size {
+            counts[i] -= size;
+            let complete = can_form_groups(counts, i, amt_needed - 1);
+            counts[i] += size;
+            if complete {
                 return true;
             }
         }
-
-        is_jantou(ts_vec) && amt_melds == 4
     }
 
-    let tiles_vec: Vec<Pai> = free_tiles.into_iter().collect();
-    if is_chiitoitsu(tiles_vec.to_owned()) || is_kokushi_musou(tiles_vec.to_owned()) {
-        return true;
+    if i < 27 && i % 9 < 7 && counts[i + 1] > 0 && counts[i + 2] > 0 {
+        counts[i] -= 1;
+        counts[i + 1] -= 1;
+        counts[i + 2] -= 1;
+        let complete = can_form_groups(counts, i, amt_needed - 1);
+        counts[i] += 1;
+        counts[i + 1] += 1;
+        counts[i + 2] += 1;
+        if complete {
+            return true;
+        }
     }
-    let possible_quad_tiles = possible_quad_pais(tiles_vec.to_owned());
-    let possible_trip_tiles = possible_trip_pais(tiles_vec.to_owned());
-    let possible_shuntsu_tiles = possible_shuntsu_starts(tiles_vec.to_owned());
-    remaining_pais_complete_hand(
-        tiles_vec,
-        possible_quad_tiles,
-        possible_trip_tiles,
-        possible_shuntsu_tiles,
-        amt_melds,
-    )
+
+    false
 }
 
 #[cfg(test)]
+// The tests spell out the expected boolean for readability.
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -951,6 +943,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn nine_gates_with_a_fourth_terminal_is_a_complete_hand() {
+        let tiles = crate::serial::tilestring_to_pais("11112345678999m").unwrap();
+        assert_eq!(is_complete_hand(tiles, 0), true);
+    }
+
+    #[test]
+    fn meld_keeps_its_exact_tiles() {
+        let tiles = crate::serial::tilestring_to_pais("640p").unwrap();
+        let meld = Meld::new(Call::Chi { called: tiles[0] }, tiles.to_owned()).unwrap();
+        assert_eq!(meld.mentsu(), Mentsu::Shuntsu);
+        assert_eq!(meld.tile(), tiles[1]);
+        assert_eq!(meld.tiles(), [tiles[1], tiles[2], tiles[0]]);
+        if let Pai::Suupai(suupai) = meld.tiles()[1] {
+            assert_eq!(suupai.akadora, true);
+        }
+        assert_eq!(meld.call().from(), Some(RelativeSeat::Kamicha));
+        assert_eq!(meld.is_concealed(), false);
+    }
+
+    #[test]
+    fn meld_tiles_must_form_the_called_group() {
+        let tiles = crate::serial::tilestring_to_pais("5550s").unwrap();
+        let pon = Call::Pon {
+            called: tiles[0],
+            from: RelativeSeat::Toimen,
+        };
+        assert_eq!(
+            Meld::new(pon, tiles.to_owned()),
+            Err(MeldError::WrongMentsu)
+        );
+        assert!(Meld::new(Call::Ankan, tiles.to_owned())
+            .unwrap()
+            .is_concealed());
+        let red_pon = Call::Pon {
+            called: tiles[3],
+            from: RelativeSeat::Toimen,
+        };
+        assert_eq!(
+            Meld::new(red_pon, tiles[..3].to_owned()),
+            Err(MeldError::MissingCalledTile)
+        );
+        assert!(Meld::new(red_pon, tiles[1..].to_owned()).is_ok());
+    }
+
     #[test]
     fn lone_pair_and_four_melds_is_a_complete_hand() {
         assert_eq!(
@@ -2,8 +2,53 @@
  * Types and functions that implement mahjong rules.
  */
 
+pub mod counts;
+pub use counts::*;
+
+pub mod decomposition;
+pub use decomposition::*;
+
+pub mod dora;
+pub use dora::*;
+
+pub mod fu;
+pub use fu::*;
+
+pub mod hand;
+pub use hand::*;
+
+pub mod machi;
+pub use machi::*;
+
 pub mod mentsu;
 pub use mentsu::*;
 
+pub mod nanikiru;
+pub use nanikiru::*;
+
+pub mod physical;
+pub use physical::*;
+
+pub mod ruleset;
+pub use ruleset::*;
+
+pub mod score;
+pub use score::*;
+
+pub mod shanten;
+pub use shanten::*;
+
 pub mod tile;
 pub use tile::*;
+
+pub mod tileset;
+pub use tileset::*;
+
+pub mod ukeire;
+pub use ukeire::*;
+
+pub mod yaku;
+pub use yaku::*;
+
+pub mod yakuman;
+pub use yakuman::*;
@@ -0,0 +1,218 @@
+/*!
+ * Functions for choosing which tile to discard.
+ */
+
+use crate::*;
+
+use std::collections::HashMap;
+
+/**
+ * How good a hand is after discarding a tile, by tile efficiency.
+ */
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiscardOption {
+    /**
+     * The tile to discard.
+     */
+    pub discard: Pai,
+
+    /**
+     * The shanten of the hand after the discard.
+     */
+    pub shanten: i8,
+
+    /**
+     * The draws that lower the shanten after the discard.
+     */
+    pub ukeire: Ukeire,
+
+    /**
+     * The highest ukeire after drawing one of the accepted tiles and discarding the best tile,
+     * or 0 if the discard leaves the hand ready.
+     */
+    pub next_ukeire: u8,
+}
+
+/**
+ * Every distinct discard of a hand that must discard, best first.
+ *
+ * Discards are ranked by lowest shanten, then most ukeire, then most ukeire after the next
+ * draw, and lastly by tile order. A red five is only offered when the hand holds no plain
+ * five of its suit.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds, including the drawn tile.
+ * * `melds`: The melds that have been made so far.
+ * * `visible`: Every other tile the player can see, e.g. discards, dora indicators and the
+ *   melds of other players.
+ */
+pub fn nanikiru(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    visible: impl IntoIterator<Item = Pai>,
+) -> Vec<DiscardOption> {
+    nanikiru_with_ruleset(free_tiles, melds, visible, &Ruleset::default())
+}
+
+/**
+ * Every distinct discard of a hand that must discard under the given rules, best first.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds, including the drawn tile.
+ * * `melds`: The melds that have been made so far.
+ * * `visible`: Every other tile the player can see.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn nanikiru_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    visible: impl IntoIterator<Item = Pai>,
+    ruleset: &Ruleset,
+) -> Vec<DiscardOption> {
+    let tiles: Vec<Pai> = free_tiles.into_iter().collect();
+    let amt_melds = u8::try_from(melds.len()).unwrap();
+    let counts: TileCounts = tiles.iter().copied().collect();
+    // Every tile of the hand is seen, the discard included.
+    let mut seen: TileCounts = visible.into_iter().collect();
+    for tile in tiles
+        .iter()
+        .copied()
+        .chain(melds.iter().flat_map(|meld| meld.tiles().to_owned()))
+    {
+        seen.add(tile);
+    }
+    let mut cache = HashMap::new();
+    let mut shanten_of = |candidate: &TileCounts| {
+        *cache.entry(*candidate).or_insert_with(|| {
+            shanten_with_ruleset(candidate.to_pais(), amt_melds, ruleset).minimum
+        })
+    };
+
+    let mut kinds: Vec<Pai> = vec![];
+    for tile in &tiles {
+        match kinds.iter_mut().find(|kind| *kind == tile) {
+            Some(kind) if kind.is_akadora() => *kind = *tile,
+            Some(_) => {}
+            None => kinds.push(*tile),
+        }
+    }
+
+    let mut result: Vec<DiscardOption> = kinds
+        .into_iter()
+        .map(|discard| {
+            let mut rest = counts;
+            rest.remove(discard);
+            let ukeire = ukeire_counts(&rest, amt_melds, &seen, &mut shanten_of);
+            let next_ukeire = if ukeire.shanten > 0 {
+                best_next_ukeire(&rest, amt_melds, &seen, &ukeire, &mut shanten_of)
+            } else {
+                0
+            };
+            DiscardOption {
+                discard,
+                shanten: ukeire.shanten,
+                ukeire,
+                next_ukeire,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| {
+        a.shanten
+            .cmp(&b.shanten)
+            .then(b.ukeire.total().cmp(&a.ukeire.total()))
+            .then(b.next_ukeire.cmp(&a.next_ukeire))
+            .then(a.discard.cmp(&b.discard))
+    });
+    result
+}
+
+/**
+ * The highest ukeire after drawing one of the accepted tiles and making the discard that
+ * keeps the lower shanten with the most ukeire.
+ */
+fn best_next_ukeire(
+    rest: &TileCounts,
+    amt_melds: u8,
+    seen: &TileCounts,
+    ukeire: &Ukeire,
+    shanten_of: &mut impl FnMut(&TileCounts) -> i8,
+) -> u8 {
+    let mut best = 0;
+    for (draw, amount) in &ukeire.tiles {
+        if *amount == 0 {
+            continue;
+        }
+        let mut drawn = *rest;
+        drawn.add(*draw);
+        let mut next_seen = *seen;
+        next_seen.add(*draw);
+        for index in (0..34).filter(|i| drawn.0[*i] > 0) {
+            let mut next = drawn;
+            next.0[index] -= 1;
+            if shanten_of(&next) >= ukeire.shanten {
+                continue;
+            }
+            let total = ukeire_counts(&next, amt_melds, &next_seen, shanten_of).total();
+            best = best.max(total);
+        }
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn isolated_honor_is_discarded_first() {
+        let result = nanikiru(tilestring_to_pais("123456789m2399p1z").unwrap(), &[], []);
+        assert_eq!(result.len(), 13);
+        assert_eq!(result[0].discard, "1z".parse().unwrap());
+        assert_eq!(result[0].shanten, 0);
+        assert_eq!(result[0].ukeire.total(), 8);
+        assert_eq!(result[0].next_ukeire, 0);
+        assert!(result.iter().skip(1).all(|option| option.shanten > 0));
+    }
+
+    #[test]
+    fn visible_tiles_change_the_ranking() {
+        // Discarding 3p waits on 6p, while discarding 7p waits on 4p.
+        let tiles = tilestring_to_pais("123456789m11z357p").unwrap();
+        let result = nanikiru(tiles.to_owned(), &[], []);
+        assert_eq!(result[0].discard, "3p".parse().unwrap());
+        assert_eq!(result[0].ukeire.total(), result[1].ukeire.total());
+        let result = nanikiru(tiles, &[], tilestring_to_pais("66p").unwrap());
+        assert_eq!(result[0].discard, "7p".parse().unwrap());
+        assert_eq!(result[0].ukeire.total(), 4);
+        assert_eq!(result[1].ukeire.total(), 2);
+    }
+
+    #[test]
+    fn second_order_acceptance_breaks_ties() {
+        // Both discards leave 15 tiles that lower the shanten, but after discarding 5m the
+        // best next draw leaves more tiles to win on.
+        let result = nanikiru(tilestring_to_pais("5m1188p122344556s").unwrap(), &[], []);
+        assert_eq!(result[0].discard, "5m".parse().unwrap());
+        assert_eq!(result[1].discard, "1s".parse().unwrap());
+        for option in &result[..2] {
+            assert_eq!(option.shanten, 1);
+            assert_eq!(option.ukeire.total(), 15);
+        }
+        assert_eq!(result[0].next_ukeire, 6);
+        assert_eq!(result[1].next_ukeire, 4);
+    }
+
+    #[test]
+    fn plain_fives_are_discarded_before_red_ones() {
+        let result = nanikiru(tilestring_to_pais("123456789m11z0559p").unwrap(), &[], []);
+        let fives: Vec<&DiscardOption> = result
+            .iter()
+            .filter(|option| option.discard == "5p".parse().unwrap())
+            .collect();
+        assert_eq!(fives.len(), 1);
+        assert!(!fives[0].discard.is_akadora());
+    }
+}
@@ -0,0 +1,205 @@
+/*!
+ * Types that identify each of the 136 tiles of a game.
+ */
+
+use crate::*;
+
+/**
+ * One of the 136 tiles of a game, numbered as in Tenhou logs.
+ *
+ * The id divided by 4 is the kind of tile in the order manzu, pinzu, souzu, ton, nan, shaa,
+ * pei, haku, hatsu, chun, and the remainder tells the four copies apart. The first copy of
+ * each five, with ids 16, 52 and 88, is the red five in games played with red fives.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u8"))]
+pub struct PhysicalTile(u8);
+
+impl PhysicalTile {
+    /**
+     * The amount of tiles in a game.
+     */
+    pub const AMT: u8 = 136;
+
+    /**
+     * The tile with the given id, if it is less than 136.
+     */
+    pub fn from_id(id: u8) -> Option<Self> {
+        (id < Self::AMT).then_some(PhysicalTile(id))
+    }
+
+    /**
+     * The given copy of a kind of tile.
+     *
+     * A red five is always copy 0, so there is no red five with any other copy and no plain
+     * five with copy 0. In games without red fives, ask for copy 0 of a five as a red five.
+     *
+     * # Arguments
+     *
+     * * `pai`: The kind of tile.
+     * * `copy`: Which of the four copies, from 0 through 3.
+     */
+    pub fn from_pai(pai: Pai, copy: u8) -> Option<Self> {
+        let is_five = matches!(pai, Pai::Suupai(suupai) if suupai.rank == 5);
+        if copy > 3 || (is_five && pai.is_akadora() != (copy == 0)) {
+            return None;
+        }
+        let kind = u8::try_from(tenhou_kind(pai)).unwrap();
+        Some(PhysicalTile(kind * 4 + copy))
+    }
+
+    /**
+     * Every tile of a game, in id order.
+     */
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..Self::AMT).map(PhysicalTile)
+    }
+
+    /**
+     * The id of this tile, from 0 through 135.
+     */
+    pub fn id(&self) -> u8 {
+        self.0
+    }
+
+    /**
+     * Which of the four copies of its kind this tile is, from 0 through 3.
+     */
+    pub fn copy(&self) -> u8 {
+        self.0 % 4
+    }
+
+    /**
+     * Whether this tile is the red five of its suit.
+     */
+    pub fn is_akadora(&self) -> bool {
+        matches!(self.0, 16 | 52 | 88)
+    }
+
+    /**
+     * The kind of this tile, a red five if it is one.
+     *
+     * In games without red fives, clear the `akadora` flag of the result.
+     */
+    pub fn pai(&self) -> Pai {
+        let kind = usize::from(self.0 / 4);
+        let index = match kind {
+            31 => 32,
+            32 => 33,
+            33 => 31,
+            _ => kind,
+        };
+        match Pai::from_index(index) {
+            Pai::Suupai(suupai) => Pai::Suupai(Suupai {
+                akadora: self.is_akadora(),
+                ..suupai
+            }),
+            jihai => jihai,
+        }
+    }
+}
+
+impl TryFrom<u8> for PhysicalTile {
+    type Error = &'static str;
+
+    fn try_from(id: u8) -> Result<Self, Self::Error> {
+        PhysicalTile::from_id(id).ok_or("tile id must be less than 136")
+    }
+}
+
+impl From<PhysicalTile> for Pai {
+    fn from(tile: PhysicalTile) -> Self {
+        tile.pai()
+    }
+}
+
+/**
+ * The kind of a tile as numbered by Tenhou, which puts the dragons in the order haku, hatsu,
+ * chun.
+ */
+fn tenhou_kind(pai: Pai) -> usize {
+    match pai.index() {
+        31 => 33,
+        32 => 31,
+        33 => 32,
+        index => index,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_follow_tenhou_numbering() {
+        assert_eq!(
+            PhysicalTile::from_id(0).unwrap().pai(),
+            "1m".parse().unwrap()
+        );
+        assert_eq!(
+            PhysicalTile::from_id(35).unwrap().pai(),
+            "9m".parse().unwrap()
+        );
+        assert_eq!(
+            PhysicalTile::from_id(108).unwrap().pai(),
+            "1z".parse().unwrap()
+        );
+        assert_eq!(
+            PhysicalTile::from_id(124).unwrap().pai(),
+            Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku))
+        );
+        assert_eq!(
+            PhysicalTile::from_id(128).unwrap().pai(),
+            Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu))
+        );
+        assert_eq!(
+            PhysicalTile::from_id(135).unwrap().pai(),
+            Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun))
+        );
+        assert_eq!(PhysicalTile::from_id(136), None);
+        assert!(PhysicalTile::try_from(200).is_err());
+    }
+
+    #[test]
+    fn first_copy_of_each_five_is_red() {
+        let red_fives: Vec<u8> = PhysicalTile::all()
+            .filter(|tile| tile.pai().is_akadora())
+            .map(|tile| tile.id())
+            .collect();
+        assert_eq!(red_fives, vec![16, 52, 88]);
+        assert_eq!(
+            PhysicalTile::from_pai("0p".parse().unwrap(), 0)
+                .unwrap()
+                .id(),
+            52
+        );
+        assert_eq!(PhysicalTile::from_pai("0p".parse().unwrap(), 1), None);
+        assert_eq!(PhysicalTile::from_pai("5p".parse().unwrap(), 0), None);
+        assert_eq!(
+            PhysicalTile::from_pai("5p".parse().unwrap(), 1)
+                .unwrap()
+                .id(),
+            53
+        );
+    }
+
+    #[test]
+    fn every_tile_round_trips() {
+        for tile in PhysicalTile::all() {
+            assert_eq!(PhysicalTile::from_pai(tile.pai(), tile.copy()), Some(tile));
+        }
+        for pai in full_set([1, 1, 1]) {
+            let tiles: Vec<PhysicalTile> = (0..4)
+                .filter_map(|copy| PhysicalTile::from_pai(pai, copy))
+                .collect();
+            assert!(!tiles.is_empty());
+            for tile in tiles {
+                assert_eq!(tile.pai(), pai);
+                assert_eq!(tile.pai().is_akadora(), pai.is_akadora());
+            }
+        }
+        let counts: TileCounts = PhysicalTile::all().map(Pai::from).collect();
+        assert!(counts.0.iter().all(|c| *c == 4));
+    }
+}
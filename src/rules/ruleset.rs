@@ -0,0 +1,51 @@
+/*!
+ * Types for choosing between rule variations.
+ */
+
+/**
+ * Rule variations that differ between clubs and online platforms.
+ *
+ * The default follows the common Japanese rules, with no double yakuman.
+ */
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Ruleset {
+    /**
+     * Whether thirteen orphans won on a thirteen-sided wait is a double yakuman.
+     */
+    pub double_kokushi_musou_juusanmen: bool,
+
+    /**
+     * Whether four concealed triplets won on a pair wait is a double yakuman.
+     */
+    pub double_suuankou_tanki: bool,
+
+    /**
+     * Whether nine gates won on a nine-sided wait is a double yakuman.
+     */
+    pub double_junsei_chuuren_poutou: bool,
+
+    /**
+     * Whether big four winds is a double yakuman.
+     */
+    pub double_daisuushii: bool,
+
+    /**
+     * Whether four copies of a tile count as two of the pairs of seven pairs.
+     */
+    pub chiitoitsu_quads_as_two_pairs: bool,
+}
+
+impl Ruleset {
+    /**
+     * Rules in which every double yakuman variant counts double.
+     */
+    pub fn with_double_yakuman() -> Self {
+        Ruleset {
+            double_kokushi_musou_juusanmen: true,
+            double_suuankou_tanki: true,
+            double_junsei_chuuren_poutou: true,
+            double_daisuushii: true,
+            ..Ruleset::default()
+        }
+    }
+}
@@ -0,0 +1,244 @@
+/*!
+ * Functions for turning han and fu into points.
+ */
+
+/**
+ * A score that is fixed regardless of fu.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Limit {
+    Mangan,
+    Haneman,
+    Baiman,
+    Sanbaiman,
+
+    /**
+     * Thirteen or more han without a yakuman.
+     */
+    KazoeYakuman,
+
+    /**
+     * The amount of yakuman the hand is worth.
+     */
+    Yakuman(u8),
+}
+
+/**
+ * The value of a hand, from which payments are calculated.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Score {
+    /**
+     * The basic points, before multiplying by the amount each player pays.
+     */
+    pub basic_points: u32,
+
+    /**
+     * The limit reached by the hand, if any.
+     */
+    pub limit: Option<Limit>,
+}
+
+/**
+ * The points owed to the winner, by whom they are owed.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Payment {
+    /**
+     * The player who dealt in pays everything.
+     */
+    Ron { discarder: u32 },
+
+    /**
+     * The dealer won by self-draw, so every other player pays the same.
+     */
+    DealerTsumo { each: u32 },
+
+    /**
+     * A non-dealer won by self-draw, so the dealer pays more than the others.
+     */
+    Tsumo { dealer: u32, non_dealer: u32 },
+}
+
+/**
+ * Everything the winner collects for a hand.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Payout {
+    /**
+     * What the other players pay, including honba.
+     */
+    pub payment: Payment,
+
+    /**
+     * The points collected from riichi sticks on the table.
+     */
+    pub riichi_sticks: u32,
+
+    /**
+     * Everything that the winner gains.
+     */
+    pub total: u32,
+}
+
+/**
+ * The score of a hand without yakuman.
+ */
+pub fn score(han: u8, fu: u8) -> Score {
+    let (basic_points, limit) = match han {
+        13.. => (8000, Some(Limit::KazoeYakuman)),
+        11..=12 => (6000, Some(Limit::Sanbaiman)),
+        8..=10 => (4000, Some(Limit::Baiman)),
+        6..=7 => (3000, Some(Limit::Haneman)),
+        _ => {
+            let basic_points = u32::from(fu) * 2u32.pow(u32::from(han) + 2);
+            if han == 5 || basic_points >= 2000 {
+                (2000, Some(Limit::Mangan))
+            } else {
+                (basic_points, None)
+            }
+        }
+    };
+    Score {
+        basic_points,
+        limit,
+    }
+}
+
+/**
+ * The score of a hand worth the given amount of yakuman.
+ */
+pub fn yakuman_score(amt_yakuman: u8) -> Score {
+    Score {
+        basic_points: 8000 * u32::from(amt_yakuman),
+        limit: Some(Limit::Yakuman(amt_yakuman)),
+    }
+}
+
+/**
+ * What the winner of a hand collects.
+ *
+ * # Arguments
+ *
+ * * `score`: The value of the hand.
+ * * `dealer`: Whether the winner is the dealer.
+ * * `tsumo`: Whether the winning tile was self-drawn.
+ * * `honba`: The amount of repeat counters, each worth 300 points.
+ * * `riichi_sticks`: The amount of riichi sticks on the table, each worth 1000 points.
+ */
+pub fn payout(score: &Score, dealer: bool, tsumo: bool, honba: u8, riichi_sticks: u8) -> Payout {
+    let basic_points = score.basic_points;
+    let honba = u32::from(honba);
+    let payment = match (dealer, tsumo) {
+        (true, false) => Payment::Ron {
+            discarder: round_up_to_hundred(basic_points * 6) + 300 * honba,
+        },
+        (false, false) => Payment::Ron {
+            discarder: round_up_to_hundred(basic_points * 4) + 300 * honba,
+        },
+        (true, true) => Payment::DealerTsumo {
+            each: round_up_to_hundred(basic_points * 2) + 100 * honba,
+        },
+        (false, true) => Payment::Tsumo {
+            dealer: round_up_to_hundred(basic_points * 2) + 100 * honba,
+            non_dealer: round_up_to_hundred(basic_points) + 100 * honba,
+        },
+    };
+    let paid = match payment {
+        Payment::Ron { discarder } => discarder,
+        Payment::DealerTsumo { each } => each * 3,
+        Payment::Tsumo { dealer, non_dealer } => dealer + non_dealer * 2,
+    };
+    let riichi_sticks = 1000 * u32::from(riichi_sticks);
+    Payout {
+        payment,
+        riichi_sticks,
+        total: paid + riichi_sticks,
+    }
+}
+
+/**
+ * Payments are always rounded up to the next 100 points.
+ */
+fn round_up_to_hundred(points: u32) -> u32 {
+    points.div_ceil(100) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_han_thirty_fu_ron_is_a_thousand() {
+        let result = payout(&score(1, 30), false, false, 0, 0);
+        assert_eq!(result.payment, Payment::Ron { discarder: 1000 });
+        assert_eq!(result.total, 1000);
+    }
+
+    #[test]
+    fn non_dealer_tsumo_splits_payments() {
+        let result = payout(&score(1, 30), false, true, 0, 0);
+        assert_eq!(
+            result.payment,
+            Payment::Tsumo {
+                dealer: 500,
+                non_dealer: 300
+            }
+        );
+        assert_eq!(result.total, 1100);
+        let result = payout(&score(2, 20), false, true, 0, 0);
+        assert_eq!(
+            result.payment,
+            Payment::Tsumo {
+                dealer: 700,
+                non_dealer: 400
+            }
+        );
+    }
+
+    #[test]
+    fn dealer_payments_are_one_and_a_half_times() {
+        let result = payout(&score(4, 30), true, false, 0, 0);
+        assert_eq!(result.payment, Payment::Ron { discarder: 11600 });
+        let result = payout(&score(4, 30), true, true, 0, 0);
+        assert_eq!(result.payment, Payment::DealerTsumo { each: 3900 });
+    }
+
+    #[test]
+    fn high_fu_reaches_mangan() {
+        assert_eq!(
+            score(3, 70),
+            Score {
+                basic_points: 2000,
+                limit: Some(Limit::Mangan)
+            }
+        );
+        assert_eq!(score(4, 30).limit, None);
+    }
+
+    #[test]
+    fn han_limits() {
+        assert_eq!(score(5, 30).limit, Some(Limit::Mangan));
+        assert_eq!(score(6, 30).limit, Some(Limit::Haneman));
+        assert_eq!(score(8, 30).limit, Some(Limit::Baiman));
+        assert_eq!(score(11, 30).limit, Some(Limit::Sanbaiman));
+        assert_eq!(score(13, 30).basic_points, 8000);
+        assert_eq!(score(13, 30).limit, Some(Limit::KazoeYakuman));
+    }
+
+    #[test]
+    fn multiple_yakuman_multiply() {
+        let result = payout(&yakuman_score(2), true, false, 0, 0);
+        assert_eq!(result.payment, Payment::Ron { discarder: 96000 });
+    }
+
+    #[test]
+    fn honba_and_riichi_sticks_are_added() {
+        let result = payout(&score(1, 30), false, false, 2, 1);
+        assert_eq!(result.payment, Payment::Ron { discarder: 1600 });
+        assert_eq!(result.riichi_sticks, 1000);
+        assert_eq!(result.total, 2600);
+        let result = payout(&score(1, 30), false, true, 1, 0);
+        assert_eq!(result.total, 1400);
+    }
+}
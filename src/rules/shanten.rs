@@ -0,0 +1,295 @@
+/*!
+ * Functions for measuring how far a collection of pais is from being a complete hand.
+ */
+
+use crate::*;
+
+/**
+ * The shanten of a hand, i.e. how many tiles away it is from tenpai.
+ *
+ * A value of 0 means tenpai and a value of -1 means the hand is already complete.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Shanten {
+    /**
+     * The lowest shanten across all of the hand forms.
+     */
+    pub minimum: i8,
+
+    /**
+     * The shanten towards four groups and a pair.
+     */
+    pub regular: i8,
+
+    /**
+     * The shanten towards seven pairs, if the hand has no melds.
+     */
+    pub chiitoitsu: Option<i8>,
+
+    /**
+     * The shanten towards thirteen orphans, if the hand has no melds.
+     */
+    pub kokushi_musou: Option<i8>,
+}
+
+/**
+ * The shanten of a collection of tiles.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ */
+pub fn shanten(free_tiles: impl IntoIterator<Item = Pai>, amt_melds: u8) -> Shanten {
+    shanten_with_ruleset(free_tiles, amt_melds, &Ruleset::default())
+}
+
+/**
+ * The shanten of a collection of tiles under the given rules.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds.
+ * * `amt_melds`: The amount of melds that have been made so far.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn shanten_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    amt_melds: u8,
+    ruleset: &Ruleset,
+) -> Shanten {
+    let mut counts = free_tiles.into_iter().collect::<TileCounts>().0;
+    let regular = regular_shanten(&mut counts, amt_melds);
+    let (chiitoitsu, kokushi_musou) = if amt_melds == 0 {
+        (
+            Some(chiitoitsu_shanten(&counts, ruleset)),
+            Some(kokushi_musou_shanten(&counts)),
+        )
+    } else {
+        (None, None)
+    };
+    let minimum = [chiitoitsu, kokushi_musou]
+        .into_iter()
+        .flatten()
+        .fold(regular, i8::min);
+    Shanten {
+        minimum,
+        regular,
+        chiitoitsu,
+        kokushi_musou,
+    }
+}
+
+/**
+ * The shanten towards seven pairs, which must be distinct unless quads count as two pairs.
+ */
+fn chiitoitsu_shanten(counts: &[u8; 34], ruleset: &Ruleset) -> i8 {
+    if ruleset.chiitoitsu_quads_as_two_pairs {
+        let amt_pairs: u8 = counts.iter().map(|c| c / 2).sum();
+        return 6 - amt_pairs.min(7) as i8;
+    }
+    let amt_pairs = counts.iter().filter(|c| **c >= 2).count() as i8;
+    let amt_kinds = counts.iter().filter(|c| **c >= 1).count() as i8;
+    6 - amt_pairs + (7 - amt_kinds).max(0)
+}
+
+/**
+ * The shanten towards one of each terminal and honor plus a pair of one of them.
+ */
+fn kokushi_musou_shanten(counts: &[u8; 34]) -> i8 {
+    let amt_kinds = YAOCHUUHAI_INDICES
+        .iter()
+        .filter(|i| counts[**i] >= 1)
+        .count() as i8;
+    let has_pair = YAOCHUUHAI_INDICES.iter().any(|i| counts[*i] >= 2);
+    13 - amt_kinds - i8::from(has_pair)
+}
+
+/**
+ * The shanten towards four groups and a pair.
+ *
+ * Each suit and the honors are split on their own, and the best combination of their splits
+ * is taken.
+ */
+fn regular_shanten(counts: &mut [u8; 34], amt_melds: u8) -> i8 {
+    let mut totals = vec![(amt_melds, 0, false)];
+    for (start, end) in [(0, 9), (9, 18), (18, 27), (27, 34)] {
+        let mut splits = vec![];
+        search_groups(counts, start, end, (0, 0, false), &mut splits);
+        splits.sort_unstable();
+        splits.dedup();
+        let mut next = vec![];
+        for (amt_mentsu, amt_taatsu, has_jantou) in &totals {
+            for (more_mentsu, more_taatsu, with_jantou) in &splits {
+                if !(*has_jantou && *with_jantou) {
+                    next.push((
+                        amt_mentsu + more_mentsu,
+                        amt_taatsu + more_taatsu,
+                        *has_jantou || *with_jantou,
+                    ));
+                }
+            }
+        }
+        next.sort_unstable();
+        next.dedup();
+        totals = next;
+    }
+    totals
+        .into_iter()
+        .map(|(amt_mentsu, amt_taatsu, has_jantou)| {
+            let useful_taatsu = amt_taatsu.min(4u8.saturating_sub(amt_mentsu));
+            8 - 2 * amt_mentsu as i8 - useful_taatsu as i8 - i8::from(has_jantou)
+        })
+        .min()
+        .unwrap()
+}
+
+/**
+ * The amount of groups and partial groups, and whether there is a pair, in a split of tiles.
+ */
+type Split = (u8, u8, bool);
+
+// Try every way of splitting the tiles from start to end into groups, partial groups, a pair
+// and leftovers.
+fn search_groups(
+    counts: &mut [u8; 34],
+    start: usize,
+    end: usize,
+    split: Split,
+    splits: &mut Vec<Split>,
+) {
+    let Some(i) = (start..end).find(|i| counts[*i] > 0) else {
+        splits.push(split);
+        return;
+    };
+    let (amt_mentsu, amt_taatsu, has_jantou) = split;
+    let is_suupai = i < 27;
+    let rank = i % 9 + 1;
+
+    for size in [4, 3] {
+        if counts[i] >= size {
+            counts[i] -= size;
+            let split = (amt_mentsu + 1, amt_taatsu, has_jantou);
+            search_groups(counts, i, end, split, splits);
+            counts[i] += size;
+        }
+    }
+    if is_suupai && rank <= 7 && counts[i + 1] > 0 && counts[i + 2] > 0 {
+        counts[i] -= 1;
+        counts[i + 1] -= 1;
+        counts[i + 2] -= 1;
+        let split = (amt_mentsu + 1, amt_taatsu, has_jantou);
+        search_groups(counts, i, end, split, splits);
+        counts[i] += 1;
+        counts[i + 1] += 1;
+        counts[i + 2] += 1;
+    }
+    if !has_jantou && counts[i] >= 2 {
+        counts[i] -= 2;
+        search_groups(counts, i, end, (amt_mentsu, amt_taatsu, true), splits);
+        counts[i] += 2;
+    }
+
+    // Partial groups only help while there is room for them.
+    if amt_mentsu + amt_taatsu < 4 {
+        let mut partners = vec![];
+        if counts[i] >= 2 {
+            partners.push(i);
+        }
+        if is_suupai && rank <= 8 && counts[i + 1] > 0 {
+            partners.push(i + 1);
+        }
+        if is_suupai && rank <= 7 && counts[i + 2] > 0 {
+            partners.push(i + 2);
+        }
+        for partner in partners {
+            counts[i] -= 1;
+            counts[partner] -= 1;
+            let split = (amt_mentsu, amt_taatsu + 1, has_jantou);
+            search_groups(counts, i, end, split, splits);
+            counts[i] += 1;
+            counts[partner] += 1;
+        }
+    }
+
+    // Leave one copy of the tile unused.
+    counts[i] -= 1;
+    search_groups(counts, i, end, split, splits);
+    counts[i] += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn complete_hand_is_minus_one_shanten() {
+        let tiles = tilestring_to_pais("123m456p789s11122z").unwrap();
+        assert_eq!(shanten(tiles, 0).regular, -1);
+    }
+
+    #[test]
+    fn one_tile_from_complete_is_tenpai() {
+        let tiles = tilestring_to_pais("123m456p789s1112z").unwrap();
+        let result = shanten(tiles, 0);
+        assert_eq!(result.regular, 0);
+        assert_eq!(result.minimum, 0);
+    }
+
+    #[test]
+    fn seven_pairs_is_complete_chiitoitsu() {
+        let tiles = tilestring_to_pais("1199m1199p11s1122z").unwrap();
+        let result = shanten(tiles, 0);
+        assert_eq!(result.chiitoitsu, Some(-1));
+        assert_eq!(result.minimum, -1);
+        assert!(result.regular > -1);
+    }
+
+    #[test]
+    fn thirteen_different_orphans_are_kokushi_tenpai() {
+        let tiles = tilestring_to_pais("19m19p19s1234567z").unwrap();
+        let result = shanten(tiles, 0);
+        assert_eq!(result.kokushi_musou, Some(0));
+        assert_eq!(result.minimum, 0);
+    }
+
+    #[test]
+    fn scattered_tiles_take_the_best_form() {
+        let tiles = tilestring_to_pais("147m258p369s1234z").unwrap();
+        let result = shanten(tiles, 0);
+        assert_eq!(result.regular, 8);
+        assert_eq!(result.chiitoitsu, Some(6));
+        assert_eq!(result.kokushi_musou, Some(7));
+        assert_eq!(result.minimum, 6);
+    }
+
+    #[test]
+    fn hands_with_melds_only_have_regular_shanten() {
+        let tiles = tilestring_to_pais("5z").unwrap();
+        let result = shanten(tiles, 4);
+        assert_eq!(result.regular, 0);
+        assert_eq!(result.chiitoitsu, None);
+        assert_eq!(result.kokushi_musou, None);
+        assert_eq!(result.minimum, 0);
+    }
+
+    #[test]
+    fn quads_count_as_two_pairs_only_by_rule() {
+        let tiles = tilestring_to_pais("1111m2299p1122s3z").unwrap();
+        assert_eq!(shanten(tiles.to_owned(), 0).chiitoitsu, Some(2));
+        let ruleset = Ruleset {
+            chiitoitsu_quads_as_two_pairs: true,
+            ..Ruleset::default()
+        };
+        let result = shanten_with_ruleset(tiles, 0, &ruleset);
+        assert_eq!(result.chiitoitsu, Some(0));
+        assert_eq!(result.minimum, 0);
+    }
+
+    #[test]
+    fn partial_groups_beyond_four_do_not_count() {
+        let tiles = tilestring_to_pais("1245m1245p1245s1z").unwrap();
+        assert_eq!(shanten(tiles, 0).regular, 4);
+    }
+}
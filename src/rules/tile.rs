@@ -2,12 +2,14 @@
  * Types that implement pais.
  */
 
+use core::cmp::Ordering;
 use core::hash::Hash;
 
 /**
  * A suit.
  */
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shoku {
     Manzu,
     Pinzu,
@@ -17,7 +19,8 @@ pub enum Shoku {
 /**
  * A wind.
  */
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Kazehai {
     Ton,
     Nan,
@@ -28,7 +31,8 @@ pub enum Kazehai {
 /**
  * A dragon.
  */
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Sangenpai {
     Chun,
     Haku,
@@ -39,6 +43,8 @@ pub enum Sangenpai {
  * A number tile.
  */
 #[derive(Clone, Copy, Debug, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedSuupai"))]
 pub struct Suupai {
     /**
      * The suit.
@@ -56,6 +62,36 @@ pub struct Suupai {
     pub akadora: bool,
 }
 
+/**
+ * A number tile as written by serde, before checking its rank and red dora.
+ */
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedSuupai {
+    shoku: Shoku,
+    rank: u8,
+    akadora: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedSuupai> for Suupai {
+    type Error = &'static str;
+
+    fn try_from(suupai: UncheckedSuupai) -> Result<Self, Self::Error> {
+        if !(1..=9).contains(&suupai.rank) {
+            return Err("rank must be from 1 through 9");
+        }
+        if suupai.akadora && suupai.rank != 5 {
+            return Err("only fives can be red");
+        }
+        Ok(Suupai {
+            shoku: suupai.shoku,
+            rank: suupai.rank,
+            akadora: suupai.akadora,
+        })
+    }
+}
+
 // Manually implement Hash to ignore akadora.
 impl Hash for Suupai {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
@@ -71,10 +107,29 @@ impl PartialEq for Suupai {
     }
 }
 
+/**
+ * Number tiles are ordered by suit, then by rank.
+ *
+ * Like equality, this ignores akadora, so a red five is neither less nor greater than a
+ * normal five of its suit. Use `sort_pais` to put red fives first.
+ */
+impl Ord for Suupai {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.shoku, self.rank).cmp(&(other.shoku, other.rank))
+    }
+}
+
+impl PartialOrd for Suupai {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 /**
  * An honor tile.
  */
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Jihai {
     Kazehai(Kazehai),
     Sangenpai(Sangenpai),
@@ -84,7 +139,182 @@ pub enum Jihai {
  * A tile.
  */
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Pai {
     Jihai(Jihai),
     Suupai(Suupai),
 }
+
+/**
+ * Tiles are ordered manzu, pinzu, souzu, winds, dragons, the same order as `Pai::index`.
+ *
+ * Like equality, this ignores akadora, so a red five is neither less nor greater than a
+ * normal five of its suit. Use `sort_pais` to put red fives first.
+ */
+impl Ord for Pai {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index().cmp(&other.index())
+    }
+}
+
+impl PartialOrd for Pai {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Pai {
+    /**
+     * Whether this is a wind or a dragon.
+     */
+    pub fn is_honor(&self) -> bool {
+        matches!(self, Pai::Jihai(_))
+    }
+
+    /**
+     * Whether this is a one or a nine of a suit.
+     */
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Pai::Suupai(suupai) if suupai.rank == 1 || suupai.rank == 9)
+    }
+
+    /**
+     * Whether this is a terminal or an honor.
+     */
+    pub fn is_yaochuuhai(&self) -> bool {
+        self.is_terminal() || self.is_honor()
+    }
+
+    /**
+     * Whether this is a two through eight of a suit.
+     */
+    pub fn is_simple(&self) -> bool {
+        !self.is_yaochuuhai()
+    }
+
+    /**
+     * Whether this is a red five.
+     */
+    pub fn is_akadora(&self) -> bool {
+        matches!(self, Pai::Suupai(Suupai { akadora: true, .. }))
+    }
+
+    /**
+     * Whether this tile is made of only green, as needed for all green.
+     */
+    pub fn is_green(&self) -> bool {
+        match self {
+            Pai::Jihai(jihai) => *jihai == Jihai::Sangenpai(Sangenpai::Hatsu),
+            Pai::Suupai(suupai) => {
+                suupai.shoku == Shoku::Souzu && [2, 3, 4, 6, 8].contains(&suupai.rank)
+            }
+        }
+    }
+
+    /**
+     * A number from 0 through 33 identifying this kind of tile.
+     *
+     * Tiles are numbered in the order manzu, pinzu, souzu, then the winds and dragons in
+     * the same order as tilestrings. Red fives share the index of normal fives.
+     */
+    pub fn index(&self) -> usize {
+        match self {
+            Pai::Suupai(suupai) => {
+                let offset = match suupai.shoku {
+                    Shoku::Manzu => 0,
+                    Shoku::Pinzu => 9,
+                    Shoku::Souzu => 18,
+                };
+                offset + usize::from(suupai.rank) - 1
+            }
+            Pai::Jihai(Jihai::Kazehai(kazehai)) => match kazehai {
+                Kazehai::Ton => 27,
+                Kazehai::Nan => 28,
+                Kazehai::Shaa => 29,
+                Kazehai::Pei => 30,
+            },
+            Pai::Jihai(Jihai::Sangenpai(sangenpai)) => match sangenpai {
+                Sangenpai::Chun => 31,
+                Sangenpai::Haku => 32,
+                Sangenpai::Hatsu => 33,
+            },
+        }
+    }
+
+    /**
+     * The tile with the given index, which is never a red five.
+     *
+     * # Panics
+     *
+     * Panics if the index is greater than 33.
+     */
+    pub fn from_index(index: usize) -> Pai {
+        match index {
+            0..=26 => Pai::Suupai(Suupai {
+                shoku: match index / 9 {
+                    0 => Shoku::Manzu,
+                    1 => Shoku::Pinzu,
+                    _ => Shoku::Souzu,
+                },
+                rank: u8::try_from(index % 9 + 1).unwrap(),
+                akadora: false,
+            }),
+            27 => Pai::Jihai(Jihai::Kazehai(Kazehai::Ton)),
+            28 => Pai::Jihai(Jihai::Kazehai(Kazehai::Nan)),
+            29 => Pai::Jihai(Jihai::Kazehai(Kazehai::Shaa)),
+            30 => Pai::Jihai(Jihai::Kazehai(Kazehai::Pei)),
+            31 => Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun)),
+            32 => Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku)),
+            33 => Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu)),
+            _ => panic!("tile index out of range: {}", index),
+        }
+    }
+}
+
+/**
+ * Sorts tiles into the order manzu, pinzu, souzu, winds, dragons.
+ *
+ * Red fives come just before the normal fives of their suit.
+ */
+pub fn sort_pais(pais: &mut [Pai]) {
+    pais.sort_by_key(|pai| (*pai, !pai.is_akadora()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn tiles_are_ordered_by_suit_then_rank() {
+        let tiles = tilestring_to_pais("1z9m1p7z1m").unwrap();
+        assert!(tiles[1] < tiles[2]);
+        assert!(tiles[2] < tiles[0]);
+        assert!(tiles[0] < tiles[3]);
+        assert_eq!(tiles.iter().max(), Some(&tiles[3]));
+        assert_eq!(tiles.iter().min(), Some(&tiles[4]));
+    }
+
+    #[test]
+    fn tiles_are_classified() {
+        let tiles = tilestring_to_pais("19m5p6s7z").unwrap();
+        assert!(tiles[0].is_terminal() && tiles[1].is_terminal());
+        assert!(tiles[2].is_simple() && !tiles[2].is_yaochuuhai());
+        assert!(tiles[3].is_green() && !tiles[2].is_green());
+        assert!(tiles[4].is_honor() && tiles[4].is_yaochuuhai() && !tiles[4].is_terminal());
+    }
+
+    #[test]
+    fn red_fives_compare_equal_to_normal_fives() {
+        let tiles = tilestring_to_pais("05p").unwrap();
+        assert_eq!(tiles[0].cmp(&tiles[1]), Ordering::Equal);
+    }
+
+    #[test]
+    fn sorting_puts_red_fives_first() {
+        let mut tiles = tilestring_to_pais("7z5505s3m").unwrap();
+        sort_pais(&mut tiles);
+        assert_eq!(tiles, tilestring_to_pais("3m5555s7z").unwrap());
+        assert!(tiles[1].is_akadora());
+    }
+}
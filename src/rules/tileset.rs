@@ -0,0 +1,126 @@
+/*!
+ * Constants and iterators for sets of tiles.
+ */
+
+use crate::*;
+
+/**
+ * The amount of kinds of tile.
+ */
+pub const AMT_PAI_KINDS: usize = 34;
+
+/**
+ * The indices of the ones and nines.
+ */
+pub const TERMINAL_INDICES: [usize; 6] = [0, 8, 9, 17, 18, 26];
+
+/**
+ * The indices of the winds and dragons.
+ */
+pub const HONOR_INDICES: [usize; 7] = [27, 28, 29, 30, 31, 32, 33];
+
+/**
+ * The indices of the terminals and honors.
+ */
+pub const YAOCHUUHAI_INDICES: [usize; 13] = [0, 8, 9, 17, 18, 26, 27, 28, 29, 30, 31, 32, 33];
+
+/**
+ * The indices of the tiles made of only green: the 2, 3, 4, 6 and 8 of souzu, and hatsu.
+ */
+pub const GREEN_INDICES: [usize; 6] = [19, 20, 21, 23, 25, 33];
+
+/**
+ * One of every kind of tile, in index order.
+ */
+pub fn all_pais() -> impl Iterator<Item = Pai> {
+    (0..AMT_PAI_KINDS).map(Pai::from_index)
+}
+
+/**
+ * One of every terminal, in index order.
+ */
+pub fn terminals() -> impl Iterator<Item = Pai> {
+    TERMINAL_INDICES.into_iter().map(Pai::from_index)
+}
+
+/**
+ * One of every honor, in index order.
+ */
+pub fn honors() -> impl Iterator<Item = Pai> {
+    HONOR_INDICES.into_iter().map(Pai::from_index)
+}
+
+/**
+ * One of every terminal and honor, in index order.
+ */
+pub fn yaochuuhai() -> impl Iterator<Item = Pai> {
+    YAOCHUUHAI_INDICES.into_iter().map(Pai::from_index)
+}
+
+/**
+ * One of every simple, in index order.
+ */
+pub fn simples() -> impl Iterator<Item = Pai> {
+    all_pais().filter(Pai::is_simple)
+}
+
+/**
+ * One of every tile made of only green, in index order.
+ */
+pub fn green_pais() -> impl Iterator<Item = Pai> {
+    GREEN_INDICES.into_iter().map(Pai::from_index)
+}
+
+/**
+ * All 136 tiles of a game, four of each kind in index order.
+ *
+ * # Arguments
+ *
+ * * `akadora`: How many of the fives of manzu, pinzu and souzu respectively are red, each
+ *   at most 4. The usual choices are `[0, 0, 0]`, `[1, 1, 1]` and `[1, 2, 1]`.
+ */
+pub fn full_set(akadora: [u8; 3]) -> Vec<Pai> {
+    let mut tiles = vec![];
+    for pai in all_pais() {
+        for copy in 0..4 {
+            let tile = match pai {
+                Pai::Suupai(suupai) if suupai.rank == 5 => Pai::Suupai(Suupai {
+                    akadora: copy < akadora[pai.index() / 9],
+                    ..suupai
+                }),
+                _ => pai,
+            };
+            tiles.push(tile);
+        }
+    }
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_agree_with_predicates() {
+        assert!(terminals().all(|t| t.is_terminal()));
+        assert!(honors().all(|t| t.is_honor()));
+        assert!(green_pais().all(|t| t.is_green()));
+        assert_eq!(all_pais().filter(Pai::is_yaochuuhai).count(), 13);
+        assert_eq!(all_pais().filter(Pai::is_green).count(), 6);
+        assert_eq!(
+            yaochuuhai().collect::<Vec<Pai>>(),
+            terminals().chain(honors()).collect::<Vec<Pai>>()
+        );
+        assert_eq!(simples().count(), 21);
+    }
+
+    #[test]
+    fn full_set_has_four_of_each_tile() {
+        let tiles = full_set([1, 2, 1]);
+        assert_eq!(tiles.len(), 136);
+        let counts: TileCounts = tiles.iter().copied().collect();
+        assert!(counts.0.iter().all(|c| *c == 4));
+        let amt_akadora = tiles.iter().filter(|t| t.is_akadora()).count();
+        assert_eq!(amt_akadora, 4);
+    }
+}
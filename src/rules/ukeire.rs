@@ -0,0 +1,209 @@
+/*!
+ * Functions for finding the draws that bring a hand closer to being complete.
+ */
+
+use crate::*;
+
+/**
+ * The draws that lower the shanten of a hand.
+ */
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Ukeire {
+    /**
+     * The shanten of the hand before drawing.
+     */
+    pub shanten: i8,
+
+    /**
+     * Each tile that lowers the shanten, along with how many of its copies are unseen.
+     *
+     * Tiles with no unseen copies are included with an amount of 0.
+     */
+    pub tiles: Vec<(Pai, u8)>,
+}
+
+impl Ukeire {
+    /**
+     * The amount of unseen tiles that lower the shanten.
+     */
+    pub fn total(&self) -> u8 {
+        self.tiles.iter().map(|(_, amount)| amount).sum()
+    }
+}
+
+/**
+ * The draws that lower the shanten of a hand, and how many of each are left to draw.
+ *
+ * Only tiles that could help some hand form are tried: tiles next to or matching a held tile
+ * for four groups and a pair, held or new kinds for seven pairs, and terminals and honors for
+ * thirteen orphans.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds, without a drawn tile.
+ * * `melds`: The melds that have been made so far. Their tiles count as seen.
+ * * `visible`: Every other tile the player can see, e.g. discards, dora indicators and the
+ *   melds of other players.
+ */
+pub fn ukeire(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    visible: impl IntoIterator<Item = Pai>,
+) -> Ukeire {
+    ukeire_with_ruleset(free_tiles, melds, visible, &Ruleset::default())
+}
+
+/**
+ * The draws that lower the shanten of a hand under the given rules, and how many of each are
+ * left to draw.
+ *
+ * # Arguments
+ *
+ * * `free_tiles`: The tiles that have not been committed to melds, without a drawn tile.
+ * * `melds`: The melds that have been made so far. Their tiles count as seen.
+ * * `visible`: Every other tile the player can see.
+ * * `ruleset`: Decides whether quads count as two pairs of seven pairs.
+ */
+pub fn ukeire_with_ruleset(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    visible: impl IntoIterator<Item = Pai>,
+    ruleset: &Ruleset,
+) -> Ukeire {
+    let counts: TileCounts = free_tiles.into_iter().collect();
+    let amt_melds = u8::try_from(melds.len()).unwrap();
+    let mut seen: TileCounts = visible.into_iter().collect();
+    for tile in counts
+        .to_pais()
+        .into_iter()
+        .chain(melds.iter().flat_map(|meld| meld.tiles().to_owned()))
+    {
+        seen.add(tile);
+    }
+    ukeire_counts(&counts, amt_melds, &seen, &mut |candidate| {
+        shanten_with_ruleset(candidate.to_pais(), amt_melds, ruleset).minimum
+    })
+}
+
+/**
+ * The draws that lower the shanten of counted tiles, using the given function to find the
+ * shanten of the tiles and of each candidate.
+ *
+ * `seen` must include the tiles themselves.
+ */
+pub(crate) fn ukeire_counts(
+    counts: &TileCounts,
+    amt_melds: u8,
+    seen: &TileCounts,
+    shanten_of: &mut impl FnMut(&TileCounts) -> i8,
+) -> Ukeire {
+    let shanten = shanten_of(counts);
+    let mut tiles = vec![];
+    if shanten < 0 {
+        return Ukeire { shanten, tiles };
+    }
+    for index in 0..34 {
+        if counts.0[index] >= 4 || !could_help(&counts.0, index, amt_melds) {
+            continue;
+        }
+        let mut candidate = *counts;
+        candidate.0[index] += 1;
+        if shanten_of(&candidate) < shanten {
+            tiles.push((Pai::from_index(index), 4u8.saturating_sub(seen.0[index])));
+        }
+    }
+    Ukeire { shanten, tiles }
+}
+
+/**
+ * Whether drawing the tile with the given index could lower the shanten of any hand form.
+ */
+fn could_help(counts: &[u8; 34], index: usize, amt_melds: u8) -> bool {
+    let near = if index < 27 {
+        let suit_start = index - index % 9;
+        let low = index.saturating_sub(2).max(suit_start);
+        let high = (index + 2).min(suit_start + 8);
+        (low..=high).any(|i| counts[i] > 0)
+    } else {
+        counts[index] > 0
+    };
+    if near {
+        return true;
+    }
+    if amt_melds > 0 {
+        return false;
+    }
+    let amt_kinds = counts.iter().filter(|c| **c > 0).count();
+    amt_kinds < 7 || YAOCHUUHAI_INDICES.contains(&index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    #[test]
+    fn ready_hand_accepts_its_winning_tiles() {
+        let result = ukeire(tilestring_to_pais("123456789m2399p").unwrap(), &[], []);
+        assert_eq!(result.shanten, 0);
+        assert_eq!(
+            result.tiles,
+            vec![("1p".parse().unwrap(), 4), ("4p".parse().unwrap(), 4)]
+        );
+        assert_eq!(result.total(), 8);
+    }
+
+    #[test]
+    fn visible_tiles_are_not_counted() {
+        let result = ukeire(
+            tilestring_to_pais("123456789m2399p").unwrap(),
+            &[],
+            tilestring_to_pais("1p1p4p").unwrap(),
+        );
+        assert_eq!(
+            result.tiles,
+            vec![("1p".parse().unwrap(), 2), ("4p".parse().unwrap(), 3)]
+        );
+        let result = ukeire(
+            tilestring_to_pais("123456789m2399p").unwrap(),
+            &[],
+            tilestring_to_pais("1111p").unwrap(),
+        );
+        assert_eq!(result.tiles[0], ("1p".parse().unwrap(), 0));
+        assert_eq!(result.total(), 4);
+    }
+
+    #[test]
+    fn held_and_melded_tiles_are_seen() {
+        let melds = ["a222p".parse().unwrap()];
+        let result = ukeire(tilestring_to_pais("456789m2378p").unwrap(), &melds, []);
+        assert_eq!(result.shanten, 1);
+        assert!(result.tiles.contains(&("2p".parse().unwrap(), 0)));
+        assert!(result.tiles.contains(&("7p".parse().unwrap(), 3)));
+    }
+
+    #[test]
+    fn agrees_with_trying_every_tile() {
+        for tilestring in [
+            "147m258p369s1234z",
+            "1199m1199p11s123z",
+            "19m19p19s1234566z",
+        ] {
+            let tiles = tilestring_to_pais(tilestring).unwrap();
+            let before = shanten(tiles.to_owned(), 0).minimum;
+            let expected: Vec<Pai> = all_pais()
+                .filter(|tile| {
+                    let mut candidate = tiles.to_owned();
+                    candidate.push(*tile);
+                    shanten(candidate, 0).minimum < before
+                })
+                .collect();
+            let result: Vec<Pai> = ukeire(tiles, &[], [])
+                .tiles
+                .into_iter()
+                .map(|(tile, _)| tile)
+                .collect();
+            assert_eq!(result, expected, "{tilestring}");
+        }
+    }
+}
@@ -0,0 +1,545 @@
+/*!
+ * Functions for finding the yaku of a complete hand.
+ */
+
+use crate::*;
+
+/**
+ * A scoring pattern worth han.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Yaku {
+    Riichi,
+    DoubleRiichi,
+    Ippatsu,
+    MenzenTsumo,
+    Haitei,
+    Houtei,
+    RinshanKaihou,
+    Chankan,
+    Tanyao,
+    Pinfu,
+    Iipeikou,
+    Yakuhai(Jihai),
+    SanshokuDoujun,
+    Ittsuu,
+    Chanta,
+    Junchan,
+    Toitoi,
+    Sanankou,
+    Honitsu,
+    Chinitsu,
+    Ryanpeikou,
+    Shousangen,
+    Honroutou,
+    Sankantsu,
+    SanshokuDoukou,
+    Chiitoitsu,
+}
+
+/**
+ * The circumstances in which a hand was won.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct WinContext {
+    /**
+     * The tile that completed the hand.
+     */
+    pub winning_tile: Pai,
+
+    /**
+     * Whether the winning tile was self-drawn rather than taken from a discard.
+     */
+    pub tsumo: bool,
+
+    /**
+     * Whether riichi was declared.
+     */
+    pub riichi: bool,
+
+    /**
+     * Whether riichi was declared on the first uninterrupted turn.
+     */
+    pub double_riichi: bool,
+
+    /**
+     * Whether the hand was won within one uninterrupted go-around of declaring riichi.
+     */
+    pub ippatsu: bool,
+
+    /**
+     * Whether the winning tile was the last tile of the wall or the discard after it.
+     */
+    pub haitei: bool,
+
+    /**
+     * Whether the winning tile was drawn as the replacement for a kan.
+     */
+    pub rinshan: bool,
+
+    /**
+     * Whether the winning tile was robbed from another player's added kan.
+     */
+    pub chankan: bool,
+
+    /**
+     * The wind of the winner's seat.
+     */
+    pub seat_wind: Kazehai,
+
+    /**
+     * The wind of the round.
+     */
+    pub round_wind: Kazehai,
+}
+
+impl WinContext {
+    /**
+     * A context with no special circumstances.
+     */
+    pub fn new(winning_tile: Pai, tsumo: bool, seat_wind: Kazehai, round_wind: Kazehai) -> Self {
+        WinContext {
+            winning_tile,
+            tsumo,
+            riichi: false,
+            double_riichi: false,
+            ippatsu: false,
+            haitei: false,
+            rinshan: false,
+            chankan: false,
+            seat_wind,
+            round_wind,
+        }
+    }
+}
+
+/**
+ * The yaku of a complete hand along with the han of each.
+ *
+ * Yakuman are not included. A thirteen orphans decomposition has no yaku.
+ *
+ * # Arguments
+ *
+ * * `decomposition`: One reading of the free tiles, including the winning tile.
+ * * `melds`: The melds that have been made.
+ * * `context`: The circumstances of the win.
+ */
+pub fn yaku(
+    decomposition: &Decomposition,
+    melds: &[Meld],
+    context: &WinContext,
+) -> Vec<(Yaku, u8)> {
+    let menzen = melds.iter().all(Meld::is_concealed);
+    let mut result = vec![];
+    if let Decomposition::KokushiMusou { .. } = decomposition {
+        return result;
+    }
+
+    if context.double_riichi {
+        result.push((Yaku::DoubleRiichi, 2));
+    } else if context.riichi {
+        result.push((Yaku::Riichi, 1));
+    }
+    if context.ippatsu && (context.riichi || context.double_riichi) {
+        result.push((Yaku::Ippatsu, 1));
+    }
+    if menzen && context.tsumo {
+        result.push((Yaku::MenzenTsumo, 1));
+    }
+    if context.haitei {
+        result.push((
+            if context.tsumo {
+                Yaku::Haitei
+            } else {
+                Yaku::Houtei
+            },
+            1,
+        ));
+    }
+    if context.rinshan && context.tsumo {
+        result.push((Yaku::RinshanKaihou, 1));
+    }
+    if context.chankan && !context.tsumo {
+        result.push((Yaku::Chankan, 1));
+    }
+
+    let tiles = hand_pais(decomposition, melds);
+    if tiles.iter().all(Pai::is_simple) {
+        result.push((Yaku::Tanyao, 1));
+    }
+    if tiles.iter().all(Pai::is_yaochuuhai)
+        && tiles.iter().any(Pai::is_honor)
+        && tiles.iter().any(Pai::is_terminal)
+    {
+        result.push((Yaku::Honroutou, 2));
+    }
+    let shokus: Vec<Shoku> = tiles
+        .iter()
+        .filter_map(|t| match t {
+            Pai::Suupai(suupai) => Some(suupai.shoku),
+            Pai::Jihai(_) => None,
+        })
+        .collect();
+    if shokus.windows(2).all(|w| w[0] == w[1]) && !shokus.is_empty() {
+        if shokus.len() == tiles.len() {
+            result.push((Yaku::Chinitsu, if menzen { 6 } else { 5 }));
+        } else {
+            result.push((Yaku::Honitsu, if menzen { 3 } else { 2 }));
+        }
+    }
+
+    let (groups, jantou) = match decomposition {
+        Decomposition::Regular { groups, jantou } => (groups, *jantou),
+        Decomposition::Chiitoitsu { .. } => {
+            result.push((Yaku::Chiitoitsu, 2));
+            return result;
+        }
+        Decomposition::KokushiMusou { .. } => return result,
+    };
+
+    let concealed_groups: Vec<(Mentsu, Pai, bool)> = groups
+        .iter()
+        .map(|group| {
+            (
+                group.mentsu,
+                group.tile,
+                is_concealed(group, groups, jantou, context),
+            )
+        })
+        .collect();
+    let all_groups: Vec<(Mentsu, Pai, bool)> = concealed_groups
+        .into_iter()
+        .chain(
+            melds
+                .iter()
+                .map(|meld| (meld.mentsu(), meld.tile(), meld.is_concealed())),
+        )
+        .collect();
+    let is_set = |mentsu: &Mentsu| !matches!(mentsu, Mentsu::Shuntsu);
+
+    // A concealed quad keeps the hand closed but still rules out pinfu.
+    if melds.is_empty()
+        && groups.iter().all(|g| g.mentsu == Mentsu::Shuntsu)
+        && !is_yakuhai(jantou, context)
+        && wait_shapes(decomposition, context.winning_tile).contains(&Wait::Ryanmen)
+    {
+        result.push((Yaku::Pinfu, 1));
+    }
+
+    if menzen {
+        let shuntsu_tiles: Vec<Pai> = groups
+            .iter()
+            .filter(|g| g.mentsu == Mentsu::Shuntsu)
+            .map(|g| g.tile)
+            .collect();
+        let mut amt_peikou = 0;
+        let mut unpaired: Vec<Pai> = vec![];
+        for tile in shuntsu_tiles {
+            if let Some(index) = unpaired.iter().position(|t| *t == tile) {
+                unpaired.remove(index);
+                amt_peikou += 1;
+            } else {
+                unpaired.push(tile);
+            }
+        }
+        match amt_peikou {
+            1 => result.push((Yaku::Iipeikou, 1)),
+            2 => result.push((Yaku::Ryanpeikou, 3)),
+            _ => {}
+        }
+    }
+
+    for (mentsu, tile, _) in all_groups.iter() {
+        if let (true, Pai::Jihai(jihai)) = (is_set(mentsu), tile) {
+            let han = yakuhai_han(*tile, context);
+            if han > 0 {
+                result.push((Yaku::Yakuhai(*jihai), han));
+            }
+        }
+    }
+
+    let has_group = |mentsu: Mentsu, shoku: Shoku, rank: u8| {
+        all_groups.iter().any(|(m, t, _)| {
+            (*m == mentsu || (mentsu == Mentsu::Koutsu && *m == Mentsu::Kantsu))
+                && *t
+                    == Pai::Suupai(Suupai {
+                        shoku,
+                        rank,
+                        akadora: false,
+                    })
+        })
+    };
+    let all_shoku = [Shoku::Manzu, Shoku::Pinzu, Shoku::Souzu];
+    if (1..=7).any(|rank| {
+        all_shoku
+            .iter()
+            .all(|s| has_group(Mentsu::Shuntsu, *s, rank))
+    }) {
+        result.push((Yaku::SanshokuDoujun, if menzen { 2 } else { 1 }));
+    }
+    if all_shoku
+        .iter()
+        .any(|s| [1, 4, 7].iter().all(|r| has_group(Mentsu::Shuntsu, *s, *r)))
+    {
+        result.push((Yaku::Ittsuu, if menzen { 2 } else { 1 }));
+    }
+    if (1..=9).any(|rank| {
+        all_shoku
+            .iter()
+            .all(|s| has_group(Mentsu::Koutsu, *s, rank))
+    }) {
+        result.push((Yaku::SanshokuDoukou, 2));
+    }
+
+    let has_shuntsu = all_groups.iter().any(|(m, _, _)| *m == Mentsu::Shuntsu);
+    let all_have_yaochuuhai = jantou.is_yaochuuhai()
+        && all_groups
+            .iter()
+            .all(|(m, t, _)| group_pais(*m, *t).into_iter().any(|t| t.is_yaochuuhai()));
+    if has_shuntsu && all_have_yaochuuhai {
+        if tiles.iter().any(Pai::is_honor) {
+            result.push((Yaku::Chanta, if menzen { 2 } else { 1 }));
+        } else {
+            result.push((Yaku::Junchan, if menzen { 3 } else { 2 }));
+        }
+    }
+
+    if all_groups.iter().all(|(m, _, _)| is_set(m)) {
+        result.push((Yaku::Toitoi, 2));
+    }
+    let amt_concealed_sets = all_groups
+        .iter()
+        .filter(|(m, _, concealed)| is_set(m) && *concealed)
+        .count();
+    if amt_concealed_sets == 3 {
+        result.push((Yaku::Sanankou, 2));
+    }
+    let amt_kantsu = all_groups
+        .iter()
+        .filter(|(m, _, _)| *m == Mentsu::Kantsu)
+        .count();
+    if amt_kantsu == 3 {
+        result.push((Yaku::Sankantsu, 2));
+    }
+
+    let amt_sangenpai_sets = all_groups
+        .iter()
+        .filter(|(m, t, _)| is_set(m) && matches!(t, Pai::Jihai(Jihai::Sangenpai(_))))
+        .count();
+    if amt_sangenpai_sets == 2 && matches!(jantou, Pai::Jihai(Jihai::Sangenpai(_))) {
+        result.push((Yaku::Shousangen, 2));
+    }
+
+    result
+}
+
+/**
+ * The tiles that make up a group.
+ */
+pub(crate) fn group_pais(mentsu: Mentsu, tile: Pai) -> Vec<Pai> {
+    match (mentsu, tile) {
+        (Mentsu::Kantsu, tile) => vec![tile; 4],
+        (Mentsu::Koutsu, tile) => vec![tile; 3],
+        (Mentsu::Shuntsu, Pai::Suupai(suupai)) => (0..3)
+            .map(|offset| {
+                Pai::Suupai(Suupai {
+                    rank: suupai.rank + offset,
+                    akadora: false,
+                    ..suupai
+                })
+            })
+            .collect(),
+        (Mentsu::Shuntsu, Pai::Jihai(_)) => vec![],
+    }
+}
+
+/**
+ * Every tile of a complete hand, including the melds.
+ */
+pub(crate) fn hand_pais(decomposition: &Decomposition, melds: &[Meld]) -> Vec<Pai> {
+    let mut tiles: Vec<Pai> = melds
+        .iter()
+        .flat_map(|meld| meld.tiles().to_owned())
+        .collect();
+    match decomposition {
+        Decomposition::Regular { groups, jantou } => {
+            tiles.extend(groups.iter().flat_map(|g| group_pais(g.mentsu, g.tile)));
+            tiles.extend([*jantou, *jantou]);
+        }
+        Decomposition::Chiitoitsu { pairs } => {
+            tiles.extend(pairs.iter().flat_map(|pair| [*pair, *pair]));
+        }
+        Decomposition::KokushiMusou { .. } => {}
+    }
+    tiles
+}
+
+/**
+ * How many han a set of the given tile is worth as yakuhai.
+ */
+fn yakuhai_han(tile: Pai, context: &WinContext) -> u8 {
+    match tile {
+        Pai::Jihai(Jihai::Sangenpai(_)) => 1,
+        Pai::Jihai(Jihai::Kazehai(kazehai)) => {
+            u8::from(kazehai == context.seat_wind) + u8::from(kazehai == context.round_wind)
+        }
+        Pai::Suupai(_) => 0,
+    }
+}
+
+/**
+ * Whether a set of the given tile would be yakuhai.
+ */
+fn is_yakuhai(tile: Pai, context: &WinContext) -> bool {
+    yakuhai_han(tile, context) > 0
+}
+
+/**
+ * Whether a group of the free tiles counts as concealed.
+ *
+ * A set completed by a discard counts as open, unless the winning tile could instead have
+ * completed a run or the pair.
+ */
+pub(crate) fn is_concealed(
+    group: &Group,
+    groups: &[Group],
+    jantou: Pai,
+    context: &WinContext,
+) -> bool {
+    if context.tsumo || group.tile != context.winning_tile || group.mentsu == Mentsu::Shuntsu {
+        return true;
+    }
+    jantou == context.winning_tile
+        || groups.iter().any(|g| {
+            g.mentsu == Mentsu::Shuntsu
+                && group_pais(g.mentsu, g.tile).contains(&context.winning_tile)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    fn meld(tilestring: &str) -> Meld {
+        tilestring.parse().unwrap()
+    }
+
+    fn evaluate(tilestring: &str, melds: &[Meld], context: &WinContext) -> Vec<Vec<(Yaku, u8)>> {
+        let tiles = tilestring_to_pais(tilestring).unwrap();
+        decompose(tiles, u8::try_from(melds.len()).unwrap())
+            .iter()
+            .map(|d| yaku(d, melds, context))
+            .collect()
+    }
+
+    #[test]
+    fn closed_all_simples_runs_with_open_wait() {
+        let mut context = WinContext::new("2m".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        context.riichi = true;
+        let result = evaluate("234m567p345678s55p", &[], &context);
+        assert_eq!(
+            result,
+            vec![vec![
+                (Yaku::Riichi, 1),
+                (Yaku::MenzenTsumo, 1),
+                (Yaku::Tanyao, 1),
+                (Yaku::Pinfu, 1),
+            ]]
+        );
+    }
+
+    #[test]
+    fn edge_wait_is_not_pinfu() {
+        let context = WinContext::new("3m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = evaluate("123m567p345678s55p", &[], &context);
+        assert_eq!(result, vec![vec![]]);
+    }
+
+    #[test]
+    fn open_hands_lose_a_han_on_some_yaku() {
+        let context = WinContext::new("3m".parse().unwrap(), false, Kazehai::Ton, Kazehai::Ton);
+        let melds = [meld("a666z"), meld("a222z")];
+        let result = evaluate("123789m99m", &melds, &context);
+        assert_eq!(
+            result,
+            vec![vec![
+                (Yaku::Honitsu, 2),
+                (Yaku::Yakuhai(Jihai::Sangenpai(Sangenpai::Haku)), 1),
+                (Yaku::Chanta, 1),
+            ]]
+        );
+    }
+
+    #[test]
+    fn double_wind_triplet_is_worth_two_han() {
+        let context = WinContext::new("5p".parse().unwrap(), false, Kazehai::Ton, Kazehai::Ton);
+        let melds = [meld("a111z")];
+        let result = evaluate("234m567p345s55p", &melds, &context);
+        assert!(result[0].contains(&(Yaku::Yakuhai(Jihai::Kazehai(Kazehai::Ton)), 2)));
+    }
+
+    #[test]
+    fn triplet_completed_by_discard_is_not_concealed() {
+        let melds = [meld("a444z")];
+        let ron = WinContext::new("5s".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("111m999p555s22z", &melds, &ron),
+            vec![vec![(Yaku::Toitoi, 2)]]
+        );
+        let tsumo = WinContext::new("5s".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("111m999p555s22z", &melds, &tsumo),
+            vec![vec![(Yaku::Toitoi, 2), (Yaku::Sanankou, 2)]]
+        );
+    }
+
+    #[test]
+    fn seven_pairs_of_terminals_and_honors() {
+        let context = WinContext::new("2z".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = evaluate("1199m1199p11s1122z", &[], &context);
+        assert_eq!(
+            result,
+            vec![vec![(Yaku::Honroutou, 2), (Yaku::Chiitoitsu, 2)]]
+        );
+    }
+
+    #[test]
+    fn two_double_runs_is_ryanpeikou_or_chiitoitsu() {
+        let context = WinContext::new("7z".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let result = evaluate("112233m445566p77z", &[], &context);
+        assert_eq!(
+            result,
+            vec![vec![(Yaku::Ryanpeikou, 3)], vec![(Yaku::Chiitoitsu, 2)]]
+        );
+    }
+
+    #[test]
+    fn straight_and_three_colour_runs() {
+        let context = WinContext::new("9m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let ittsuu = evaluate("123456789m234p11z", &[], &context);
+        assert!(ittsuu[0].contains(&(Yaku::Ittsuu, 2)));
+        let sanshoku = evaluate("234m234p234s678s11z", &[], &context);
+        assert!(sanshoku[0].contains(&(Yaku::SanshokuDoujun, 2)));
+    }
+
+    #[test]
+    fn two_dragon_triplets_and_a_dragon_pair_is_shousangen() {
+        let context = WinContext::new("3m".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        let result = evaluate("123m555666z77z", &[meld("l456p")], &context);
+        assert!(result[0].contains(&(Yaku::Shousangen, 2)));
+        assert!(result[0].contains(&(Yaku::Yakuhai(Jihai::Sangenpai(Sangenpai::Chun)), 1)));
+    }
+
+    #[test]
+    fn concealed_quad_keeps_the_hand_closed() {
+        let context = WinContext::new("2m".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        let closed = evaluate("234m567p345s55p", &[meld("8888s")], &context);
+        assert!(closed[0].contains(&(Yaku::MenzenTsumo, 1)));
+        assert!(closed[0].contains(&(Yaku::Tanyao, 1)));
+        assert!(!closed[0].contains(&(Yaku::Pinfu, 1)));
+        let open = evaluate("234m567p345s55p", &[meld("r8888s")], &context);
+        assert!(!open[0].contains(&(Yaku::MenzenTsumo, 1)));
+        assert!(open[0].contains(&(Yaku::Tanyao, 1)));
+    }
+}
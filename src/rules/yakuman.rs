@@ -0,0 +1,268 @@
+/*!
+ * Functions for finding the yakuman of a complete hand.
+ */
+
+use crate::rules::yaku::{hand_pais, is_concealed};
+use crate::*;
+
+/**
+ * A limit hand.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Yakuman {
+    KokushiMusou,
+    KokushiMusouJuusanmen,
+    Suuankou,
+    SuuankouTanki,
+    Daisangen,
+    Tsuuiisou,
+    Ryuuiisou,
+    Chinroutou,
+    Shousuushii,
+    Daisuushii,
+    ChuurenPoutou,
+    JunseiChuurenPoutou,
+    Suukantsu,
+}
+
+/**
+ * The yakuman of a complete hand along with how many yakuman each is worth.
+ *
+ * # Arguments
+ *
+ * * `decomposition`: One reading of the free tiles, including the winning tile.
+ * * `melds`: The melds that have been made.
+ * * `context`: The circumstances of the win.
+ * * `ruleset`: Decides which yakuman count double.
+ */
+pub fn yakuman(
+    decomposition: &Decomposition,
+    melds: &[Meld],
+    context: &WinContext,
+    ruleset: &Ruleset,
+) -> Vec<(Yakuman, u8)> {
+    let double = |is_double: bool| if is_double { 2 } else { 1 };
+    let mut result = vec![];
+
+    if let Decomposition::KokushiMusou { jantou } = decomposition {
+        if *jantou == context.winning_tile {
+            result.push((
+                Yakuman::KokushiMusouJuusanmen,
+                double(ruleset.double_kokushi_musou_juusanmen),
+            ));
+        } else {
+            result.push((Yakuman::KokushiMusou, 1));
+        }
+        return result;
+    }
+
+    let tiles = hand_pais(decomposition, melds);
+    if tiles.iter().all(Pai::is_honor) {
+        result.push((Yakuman::Tsuuiisou, 1));
+    }
+    if tiles.iter().all(Pai::is_green) {
+        result.push((Yakuman::Ryuuiisou, 1));
+    }
+    if tiles.iter().all(Pai::is_terminal) {
+        result.push((Yakuman::Chinroutou, 1));
+    }
+    if melds.is_empty() {
+        if let Some(junsei) = chuuren_poutou(&tiles, context.winning_tile) {
+            if junsei {
+                result.push((
+                    Yakuman::JunseiChuurenPoutou,
+                    double(ruleset.double_junsei_chuuren_poutou),
+                ));
+            } else {
+                result.push((Yakuman::ChuurenPoutou, 1));
+            }
+        }
+    }
+
+    let Decomposition::Regular { groups, jantou } = decomposition else {
+        return result;
+    };
+    let jantou = *jantou;
+
+    let amt_concealed_sets = groups
+        .iter()
+        .filter(|g| g.mentsu != Mentsu::Shuntsu && is_concealed(g, groups, jantou, context))
+        .count()
+        + melds.iter().filter(|m| m.is_concealed()).count();
+    if amt_concealed_sets == 4 {
+        if jantou == context.winning_tile {
+            result.push((
+                Yakuman::SuuankouTanki,
+                double(ruleset.double_suuankou_tanki),
+            ));
+        } else {
+            result.push((Yakuman::Suuankou, 1));
+        }
+    }
+
+    let sets: Vec<Pai> = groups
+        .iter()
+        .map(|g| (g.mentsu, g.tile))
+        .chain(melds.iter().map(|m| (m.mentsu(), m.tile())))
+        .filter(|(mentsu, _)| *mentsu != Mentsu::Shuntsu)
+        .map(|(_, tile)| tile)
+        .collect();
+    let amt_sangenpai_sets = sets
+        .iter()
+        .filter(|t| matches!(t, Pai::Jihai(Jihai::Sangenpai(_))))
+        .count();
+    if amt_sangenpai_sets == 3 {
+        result.push((Yakuman::Daisangen, 1));
+    }
+    let amt_kazehai_sets = sets
+        .iter()
+        .filter(|t| matches!(t, Pai::Jihai(Jihai::Kazehai(_))))
+        .count();
+    if amt_kazehai_sets == 4 {
+        result.push((Yakuman::Daisuushii, double(ruleset.double_daisuushii)));
+    } else if amt_kazehai_sets == 3 && matches!(jantou, Pai::Jihai(Jihai::Kazehai(_))) {
+        result.push((Yakuman::Shousuushii, 1));
+    }
+
+    let amt_kantsu = groups
+        .iter()
+        .map(|g| g.mentsu)
+        .chain(melds.iter().map(Meld::mentsu))
+        .filter(|mentsu| *mentsu == Mentsu::Kantsu)
+        .count();
+    if amt_kantsu == 4 {
+        result.push((Yakuman::Suukantsu, 1));
+    }
+
+    result
+}
+
+/**
+ * If the tiles form nine gates, whether they were waiting on all nine tiles of the suit.
+ */
+fn chuuren_poutou(tiles: &[Pai], winning_tile: Pai) -> Option<bool> {
+    let Some(Pai::Suupai(first)) = tiles.first() else {
+        return None;
+    };
+    let mut ranks = [0; 10];
+    for tile in tiles {
+        match tile {
+            Pai::Suupai(suupai) if suupai.shoku == first.shoku => {
+                ranks[usize::from(suupai.rank)] += 1;
+            }
+            _ => return None,
+        }
+    }
+    let base = [0, 3, 1, 1, 1, 1, 1, 1, 1, 3];
+    if tiles.len() != 14 || (1..=9).any(|rank| ranks[rank] < base[rank]) {
+        return None;
+    }
+    Some(match winning_tile {
+        Pai::Suupai(winner) => {
+            winner.shoku == first.shoku
+                && ranks[usize::from(winner.rank)] > base[usize::from(winner.rank)]
+        }
+        Pai::Jihai(_) => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serial::tilestring_to_pais;
+
+    fn evaluate(tilestring: &str, context: &WinContext, ruleset: &Ruleset) -> Vec<(Yakuman, u8)> {
+        let tiles = tilestring_to_pais(tilestring).unwrap();
+        decompose(tiles, 0)
+            .iter()
+            .flat_map(|d| yakuman(d, &[], context, ruleset))
+            .collect()
+    }
+
+    #[test]
+    fn thirteen_sided_kokushi_can_count_double() {
+        let context = WinContext::new("7z".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("19m19p19s12345677z", &context, &Ruleset::default()),
+            vec![(Yakuman::KokushiMusouJuusanmen, 1)]
+        );
+        assert_eq!(
+            evaluate(
+                "19m19p19s12345677z",
+                &context,
+                &Ruleset::with_double_yakuman()
+            ),
+            vec![(Yakuman::KokushiMusouJuusanmen, 2)]
+        );
+        let context = WinContext::new("1m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("19m19p19s12345677z", &context, &Ruleset::default()),
+            vec![(Yakuman::KokushiMusou, 1)]
+        );
+    }
+
+    #[test]
+    fn four_concealed_triplets_depend_on_the_wait() {
+        let tiles = "111m999p555s22333z";
+        let ruleset = Ruleset::with_double_yakuman();
+        let tsumo = WinContext::new("5s".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate(tiles, &tsumo, &ruleset),
+            vec![(Yakuman::Suuankou, 1)]
+        );
+        let ron = WinContext::new("5s".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(evaluate(tiles, &ron, &ruleset), vec![]);
+        let tanki = WinContext::new("2z".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate(tiles, &tanki, &ruleset),
+            vec![(Yakuman::SuuankouTanki, 2)]
+        );
+    }
+
+    #[test]
+    fn three_dragon_triplets_is_daisangen() {
+        let context = WinContext::new("1p".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        let melds = ["a555z".parse().unwrap()];
+        let tiles = tilestring_to_pais("666777z123m11p").unwrap();
+        let decompositions = decompose(tiles, 1);
+        assert_eq!(
+            yakuman(&decompositions[0], &melds, &context, &Ruleset::default()),
+            vec![(Yakuman::Daisangen, 1)]
+        );
+    }
+
+    #[test]
+    fn nine_gates_is_pure_when_waiting_on_nine_tiles() {
+        let ruleset = Ruleset::with_double_yakuman();
+        let junsei = WinContext::new("1m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert!(evaluate("11112345678999m", &junsei, &ruleset)
+            .contains(&(Yakuman::JunseiChuurenPoutou, 2)));
+        let plain = WinContext::new("9m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert!(
+            evaluate("11112345678999m", &plain, &ruleset).contains(&(Yakuman::ChuurenPoutou, 1))
+        );
+    }
+
+    #[test]
+    fn green_and_wind_hands() {
+        let context = WinContext::new("8s".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("223344666888s77z", &context, &Ruleset::default()),
+            vec![(Yakuman::Ryuuiisou, 1)]
+        );
+        let context = WinContext::new("3m".parse().unwrap(), false, Kazehai::Nan, Kazehai::Ton);
+        assert_eq!(
+            evaluate("111222333z44z123m", &context, &Ruleset::default()),
+            vec![(Yakuman::Shousuushii, 1)]
+        );
+    }
+
+    #[test]
+    fn big_four_winds_with_quads() {
+        let context = WinContext::new("5z".parse().unwrap(), true, Kazehai::Nan, Kazehai::Ton);
+        let result = evaluate("111122223333444455z", &context, &Ruleset::default());
+        assert!(result.contains(&(Yakuman::Daisuushii, 1)));
+        assert!(result.contains(&(Yakuman::Suukantsu, 1)));
+        assert!(result.contains(&(Yakuman::Tsuuiisou, 1)));
+    }
+}
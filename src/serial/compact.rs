@@ -0,0 +1,155 @@
+/*!
+ * Serde helpers for writing tiles, melds and hands as tilestrings instead of nested objects.
+ *
+ * Use them on fields with `#[serde(with = "mahjong::serial::compact")]` for a single value, or
+ * `#[serde(with = "mahjong::serial::compact::vec")]` for a `Vec` of values.
+ */
+
+use crate::serial::*;
+
+use core::fmt::Display;
+use core::str::FromStr;
+
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serializer};
+
+/**
+ * Serializes a value as its tilestring, e.g. "0p".
+ */
+pub fn serialize<T: Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+/**
+ * Deserializes a value from its tilestring.
+ */
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: FromStr<Err = ParseError>,
+    D: Deserializer<'de>,
+{
+    let tilestring = String::deserialize(deserializer)?;
+    tilestring.parse().map_err(D::Error::custom)
+}
+
+/**
+ * Serde helpers for a `Vec` of values, each written as its own tilestring.
+ */
+pub mod vec {
+    use super::*;
+
+    use serde::ser::SerializeSeq;
+
+    /**
+     * Serializes values as a sequence of tilestrings, e.g. ["1m", "0p"].
+     */
+    pub fn serialize<T: Display, S: Serializer>(
+        values: &[T],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_string())?;
+        }
+        seq.end()
+    }
+
+    /**
+     * Deserializes values from a sequence of tilestrings.
+     */
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        T: FromStr<Err = ParseError>,
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|tilestring| tilestring.parse().map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Serialize;
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    struct Discards {
+        #[serde(with = "crate::serial::compact")]
+        last: Pai,
+        #[serde(with = "crate::serial::compact::vec")]
+        all: Vec<Pai>,
+        #[serde(with = "crate::serial::compact::vec")]
+        melds: Vec<Meld>,
+    }
+
+    #[test]
+    fn tiles_are_written_as_tilestrings() {
+        let discards = Discards {
+            last: "0p".parse().unwrap(),
+            all: tilestring_to_pais("10p7z").unwrap(),
+            melds: vec!["l406s".parse().unwrap()],
+        };
+        let json = serde_json::to_string(&discards).unwrap();
+        assert_eq!(
+            json,
+            r#"{"last":"0p","all":["1p","0p","7z"],"melds":["l406s"]}"#
+        );
+        assert_eq!(serde_json::from_str::<Discards>(&json).unwrap(), discards);
+    }
+
+    #[test]
+    fn invalid_tilestrings_are_errors() {
+        let json = r#"{"last":"8z","all":[],"melds":[]}"#;
+        let error = serde_json::from_str::<Discards>(json).unwrap_err();
+        assert!(error.to_string().contains("not an honor"));
+    }
+
+    #[test]
+    fn tiles_are_objects_by_default() {
+        let pai: Pai = "0p".parse().unwrap();
+        let json = serde_json::to_string(&pai).unwrap();
+        assert_eq!(
+            json,
+            r#"{"Suupai":{"shoku":"Pinzu","rank":5,"akadora":true}}"#
+        );
+        assert_eq!(serde_json::from_str::<Pai>(&json).unwrap(), pai);
+    }
+
+    #[test]
+    fn ranks_are_checked_when_deserialized() {
+        for rank in [0, 10] {
+            let json = format!(r#"{{"Suupai":{{"shoku":"Souzu","rank":{rank},"akadora":false}}}}"#);
+            let error = serde_json::from_str::<Pai>(&json).unwrap_err();
+            assert!(error.to_string().contains("rank must be from 1 through 9"));
+        }
+        let json = r#"{"Suupai":{"shoku":"Souzu","rank":3,"akadora":true}}"#;
+        let error = serde_json::from_str::<Pai>(json).unwrap_err();
+        assert!(error.to_string().contains("only fives can be red"));
+        let json = r#"{"Suupai":{"shoku":"Souzu","rank":5,"akadora":true}}"#;
+        assert!(serde_json::from_str::<Pai>(json).unwrap().is_akadora());
+    }
+
+    #[test]
+    fn physical_tiles_are_checked_when_deserialized() {
+        let tile = PhysicalTile::from_id(52).unwrap();
+        assert_eq!(serde_json::to_string(&tile).unwrap(), "52");
+        assert_eq!(serde_json::from_str::<PhysicalTile>("52").unwrap(), tile);
+        assert!(serde_json::from_str::<PhysicalTile>("200").is_err());
+    }
+
+    #[test]
+    fn melds_are_checked_when_deserialized() {
+        let meld: Meld = "a505s".parse().unwrap();
+        let json = serde_json::to_string(&meld).unwrap();
+        assert_eq!(serde_json::from_str::<Meld>(&json).unwrap(), meld);
+        let chi = r#"{"call":"Ankan","tiles":[
+            {"Suupai":{"shoku":"Souzu","rank":1,"akadora":false}},
+            {"Suupai":{"shoku":"Souzu","rank":2,"akadora":false}},
+            {"Suupai":{"shoku":"Souzu","rank":3,"akadora":false}}
+        ]}"#;
+        assert!(serde_json::from_str::<Meld>(chi).is_err());
+    }
+}
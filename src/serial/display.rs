@@ -0,0 +1,150 @@
+/*!
+ * Textual forms of tiles, melds and hands that match the tilestring format.
+ */
+
+use crate::serial::*;
+
+use core::fmt;
+use core::str::FromStr;
+
+impl fmt::Display for Pai {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&pais_to_tilestring([*self]))
+    }
+}
+
+impl FromStr for Pai {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match tilestring_to_pais(s)?.as_slice() {
+            [pai] => Ok(*pai),
+            _ => Err(ParseError {
+                offset: 0,
+                reason: ParseErrorReason::ExpectedOneTile,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Suupai {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Pai::Suupai(*self).fmt(f)
+    }
+}
+
+impl FromStr for Suupai {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            Pai::Suupai(suupai) => Ok(suupai),
+            Pai::Jihai(_) => Err(ParseError {
+                offset: 0,
+                reason: ParseErrorReason::UnexpectedJihai,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Jihai {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Pai::Jihai(*self).fmt(f)
+    }
+}
+
+impl FromStr for Jihai {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse()? {
+            Pai::Jihai(jihai) => Ok(jihai),
+            Pai::Suupai(_) => Err(ParseError {
+                offset: 0,
+                reason: ParseErrorReason::UnexpectedSuupai,
+            }),
+        }
+    }
+}
+
+/**
+ * A meld is written as in a hand, without the brackets, e.g. l406s.
+ */
+impl fmt::Display for Meld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&meld_to_tilestring(self, &Notation::default()))
+    }
+}
+
+impl FromStr for Meld {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_meld(s, 0, &Notation::default(), &mut TileCounts::new())
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&hand_to_tilestring(
+            self.free_tiles().to_owned(),
+            self.melds(),
+        ))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (free_tiles, melds) = tilestring_to_hand(s)?;
+        Hand::new(free_tiles, &melds).map_err(|error| ParseError {
+            offset: 0,
+            reason: ParseErrorReason::InvalidHand(error),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_display_as_tilestrings() {
+        let red_five: Pai = "0p".parse().unwrap();
+        assert_eq!(red_five.to_string(), "0p");
+        assert_eq!(
+            "5z".parse::<Jihai>().unwrap(),
+            Jihai::Sangenpai(Sangenpai::Chun)
+        );
+        assert_eq!("9s".parse::<Suupai>().unwrap().to_string(), "9s");
+    }
+
+    #[test]
+    fn only_single_tiles_of_the_right_kind_parse() {
+        let reason = |result: Result<Pai, ParseError>| result.unwrap_err().reason;
+        assert_eq!(reason("12m".parse()), ParseErrorReason::ExpectedOneTile);
+        assert_eq!(reason("".parse()), ParseErrorReason::ExpectedOneTile);
+        assert_eq!(
+            "1z".parse::<Suupai>().unwrap_err().reason,
+            ParseErrorReason::UnexpectedJihai
+        );
+        assert_eq!(
+            "1m".parse::<Jihai>().unwrap_err().reason,
+            ParseErrorReason::UnexpectedSuupai
+        );
+    }
+
+    #[test]
+    fn melds_and_hands_round_trip() {
+        let meld: Meld = "l604m".parse().unwrap();
+        assert_eq!(meld.to_string(), "l640m");
+        let hand: Hand = "123m456p11z[l406s][5555z]".parse().unwrap();
+        assert_eq!(hand.melds()[0], "l406s".parse().unwrap());
+        assert_eq!(hand.to_string(), "123m456p11z[l406s][5555z]");
+        assert_eq!(
+            "123m".parse::<Hand>().unwrap_err().reason,
+            ParseErrorReason::InvalidHand(HandError::WrongTileCount)
+        );
+    }
+}
@@ -0,0 +1,117 @@
+/*!
+ * Functions for converting tiles to and from the characters of the Unicode Mahjong Tiles block.
+ */
+
+use crate::serial::*;
+
+/**
+ * The first character of the Mahjong Tiles block, the east wind.
+ */
+const FIRST_GLYPH: u32 = 0x1F000;
+
+/**
+ * The offsets from `FIRST_GLYPH` of the glyphs of the tiles, in `Pai::index` order.
+ */
+const GLYPH_OFFSETS: [u32; 34] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 15, // manzu
+    25, 26, 27, 28, 29, 30, 31, 32, 33, // pinzu
+    16, 17, 18, 19, 20, 21, 22, 23, 24, // souzu
+    0, 1, 2, 3, // ton, nan, shaa, pei
+    4, 6, 5, // chun, haku, hatsu
+];
+
+/**
+ * The Unicode character of a tile.
+ *
+ * Unicode has no red fives, so a red five has the same character as any other five.
+ */
+pub fn pai_to_glyph(pai: Pai) -> char {
+    char::from_u32(FIRST_GLYPH + GLYPH_OFFSETS[pai.index()]).unwrap()
+}
+
+/**
+ * Converts tiles to their Unicode characters, keeping their order.
+ */
+pub fn pais_to_glyphs(pais: impl IntoIterator<Item = Pai>) -> String {
+    pais.into_iter().map(pai_to_glyph).collect()
+}
+
+/**
+ * Converts a string of Unicode tile characters to tiles.
+ *
+ * Whitespace and the emoji variation selector, which some platforms add after the red
+ * dragon, are skipped.
+ */
+pub fn glyphs_to_pais(glyphs: &str) -> Result<Vec<Pai>, ParseError> {
+    let mut seen = TileCounts::new();
+    let mut pais = vec![];
+    for (offset, c) in glyphs.char_indices() {
+        if c.is_whitespace() || c == '\u{FE0F}' {
+            continue;
+        }
+        let index = u32::from(c)
+            .checked_sub(FIRST_GLYPH)
+            .and_then(|glyph_offset| GLYPH_OFFSETS.iter().position(|o| *o == glyph_offset));
+        let Some(index) = index else {
+            return Err(ParseError {
+                offset,
+                reason: ParseErrorReason::UnknownGlyph(c),
+            });
+        };
+        let pai = Pai::from_index(index);
+        if seen[pai] >= 4 {
+            return Err(ParseError {
+                offset,
+                reason: ParseErrorReason::TooManyCopies(pai),
+            });
+        }
+        seen.add(pai);
+        pais.push(pai);
+    }
+    Ok(pais)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_have_their_unicode_characters() {
+        let pais = tilestring_to_pais("19m19p19s1234567z").unwrap();
+        assert_eq!(pais_to_glyphs(pais), "🀇🀏🀙🀡🀐🀘🀀🀁🀂🀃🀄🀆🀅");
+    }
+
+    #[test]
+    fn every_tile_round_trips() {
+        let pais: Vec<Pai> = all_pais().collect();
+        assert_eq!(glyphs_to_pais(&pais_to_glyphs(pais.to_owned())), Ok(pais));
+    }
+
+    #[test]
+    fn red_fives_become_normal_fives() {
+        let pais = tilestring_to_pais("0m").unwrap();
+        assert_eq!(pais_to_glyphs(pais), "🀋");
+    }
+
+    #[test]
+    fn spaces_and_variation_selectors_are_skipped() {
+        let pais = glyphs_to_pais("🀄\u{FE0F} 🀄").unwrap();
+        assert_eq!(pais, tilestring_to_pais("55z").unwrap());
+    }
+
+    #[test]
+    fn other_characters_are_errors() {
+        assert_eq!(
+            glyphs_to_pais("🀇x"),
+            Err(ParseError {
+                offset: 4,
+                reason: ParseErrorReason::UnknownGlyph('x')
+            })
+        );
+        assert_eq!(
+            glyphs_to_pais("🀪").unwrap_err().reason,
+            ParseErrorReason::UnknownGlyph('🀪')
+        );
+        assert_eq!(glyphs_to_pais("🀇🀇🀇🀇🀇").unwrap_err().offset, 16);
+    }
+}
@@ -4,10 +4,22 @@
 
 use crate::rules::*;
 
-use regex::Regex;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+pub mod compact;
+
+mod display;
+
+pub mod glyph;
+pub use glyph::*;
+
+pub mod notation;
+pub use notation::*;
 
 /**
- * Whether the given candidate is a valid tilestring e.g. 111406p33377z789s.
+ * Whether the given candidate is a valid tilestring e.g. 111406p33377z789s, optionally with
+ * called melds e.g. 123m[a555z].
  *
  * # Conventions
  * * m/w/c -> manzu
@@ -19,68 +31,462 @@ use regex::Regex;
  * * 5z -> chun
  */
 pub fn is_tilestring(candidate: &str) -> bool {
-    let re = Regex::new(r"^(([0-9]+[mwcpdsb])|([1-7]+[zh]))*$").unwrap();
-    re.captures(candidate).is_some()
+    is_tilestring_with_notation(candidate, &Notation::default())
+}
+
+/**
+ * Whether the given candidate is a valid tilestring in the given notation.
+ */
+pub fn is_tilestring_with_notation(candidate: &str, notation: &Notation) -> bool {
+    tilestring_to_hand_with_notation(candidate, notation).is_ok()
+}
+
+/**
+ * Why a tilestring could not be parsed.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParseErrorReason {
+    /**
+     * A character that is neither a digit nor a known suit letter.
+     */
+    UnknownSuit(char),
+
+    /**
+     * Digits at the end of the tilestring with no suit letter after them.
+     */
+    MissingSuit,
+
+    /**
+     * A suit letter with no digits before it.
+     */
+    MissingDigits,
+
+    /**
+     * An honor digit other than 1 to 7.
+     */
+    InvalidHonorDigit(char),
+
+    /**
+     * The red five digit followed by the honor suit letter.
+     */
+    RedFiveHonor,
+
+    /**
+     * A digit that is not a rank, such as 0 in a notation with a different red five digit.
+     */
+    InvalidRank(char),
+
+    /**
+     * A fifth copy of a tile.
+     */
+    TooManyCopies(Pai),
+
+    /**
+     * A bracket that opens a meld without a bracket to close it.
+     */
+    UnclosedMeld,
+
+    /**
+     * Tiles in brackets that do not form the group made by their call.
+     */
+    InvalidMeld(MeldError),
+
+    /**
+     * Tiles and melds that cannot be a hand.
+     */
+    InvalidHand(HandError),
+
+    /**
+     * A call that cannot be made, such as a chi from a player other than the kamicha.
+     */
+    InvalidCall,
+
+    /**
+     * A character that is not a Unicode mahjong tile.
+     */
+    UnknownGlyph(char),
+
+    /**
+     * Anything other than exactly one tile where a single tile was expected.
+     */
+    ExpectedOneTile,
+
+    /**
+     * An honor where a numbered tile was expected.
+     */
+    UnexpectedJihai,
+
+    /**
+     * A numbered tile where an honor was expected.
+     */
+    UnexpectedSuupai,
 }
 
+/**
+ * A problem with a tilestring, at the byte offset where it was found.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub reason: ParseErrorReason,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            ParseErrorReason::UnknownSuit(letter) => write!(f, "unknown suit '{letter}'"),
+            ParseErrorReason::MissingSuit => write!(f, "digits without a suit"),
+            ParseErrorReason::MissingDigits => write!(f, "suit without digits"),
+            ParseErrorReason::InvalidHonorDigit(digit) => {
+                write!(f, "'{digit}' is not an honor, expected 1 to 7")
+            }
+            ParseErrorReason::RedFiveHonor => write!(f, "honors have no red five"),
+            ParseErrorReason::InvalidRank(digit) => write!(f, "'{digit}' is not a rank"),
+            ParseErrorReason::TooManyCopies(pai) => write!(f, "more than four copies of {pai}"),
+            ParseErrorReason::UnclosedMeld => write!(f, "meld is missing ']'"),
+            ParseErrorReason::InvalidMeld(error) => write!(f, "invalid meld, {error}"),
+            ParseErrorReason::InvalidHand(error) => write!(f, "invalid hand, {error}"),
+            ParseErrorReason::InvalidCall => write!(f, "call cannot be made"),
+            ParseErrorReason::UnknownGlyph(c) => write!(f, "'{c}' is not a tile"),
+            ParseErrorReason::ExpectedOneTile => write!(f, "expected exactly one tile"),
+            ParseErrorReason::UnexpectedJihai => write!(f, "expected a numbered tile"),
+            ParseErrorReason::UnexpectedSuupai => write!(f, "expected an honor"),
+        }?;
+        write!(f, " at offset {}", self.offset)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 /**
  * Converts a tilestring to tiles.
  */
-pub fn tilestring_to_pais(tilestring: &str) -> Option<Vec<Pai>> {
-    if !is_tilestring(tilestring) {
-        return None;
+pub fn tilestring_to_pais(tilestring: &str) -> Result<Vec<Pai>, ParseError> {
+    tilestring_to_pais_with_notation(tilestring, &Notation::default())
+}
+
+/**
+ * Converts a tilestring written in the given notation to tiles.
+ */
+pub fn tilestring_to_pais_with_notation(
+    tilestring: &str,
+    notation: &Notation,
+) -> Result<Vec<Pai>, ParseError> {
+    parse_pais(tilestring, 0, notation, &mut TileCounts::new())
+}
+
+/**
+ * Converts a tilestring to tile counts.
+ */
+pub fn tilestring_to_counts(tilestring: &str) -> Result<TileCounts, ParseError> {
+    tilestring_to_pais(tilestring).map(|pais| pais.into_iter().collect())
+}
+
+/**
+ * Parses the tiles of a tilestring that starts at the given offset of the whole input.
+ *
+ * `seen` holds the tiles parsed so far from the whole input, so that copies are counted
+ * across every part of it.
+ */
+fn parse_pais(
+    tilestring: &str,
+    start: usize,
+    notation: &Notation,
+    seen: &mut TileCounts,
+) -> Result<Vec<Pai>, ParseError> {
+    let mut pais = vec![];
+    let mut digits: Vec<(usize, char)> = vec![];
+    for (offset, c) in tilestring.char_indices() {
+        if notation.is_digit(c) {
+            digits.push((start + offset, c));
+            continue;
+        }
+        if !notation.is_suit_letter(c) {
+            return Err(ParseError {
+                offset: start + offset,
+                reason: ParseErrorReason::UnknownSuit(c),
+            });
+        }
+        if digits.is_empty() {
+            return Err(ParseError {
+                offset: start + offset,
+                reason: ParseErrorReason::MissingDigits,
+            });
+        }
+        for (digit_offset, digit) in digits.drain(..) {
+            let error = |reason| ParseError {
+                offset: digit_offset,
+                reason,
+            };
+            let pai = if notation.jihai().contains(&c) {
+                if digit == notation.red_five() {
+                    return Err(error(ParseErrorReason::RedFiveHonor));
+                }
+                match notation.honor(digit) {
+                    Some(jihai) => Pai::Jihai(jihai),
+                    None => return Err(error(ParseErrorReason::InvalidHonorDigit(digit))),
+                }
+            } else {
+                let shoku = if notation.manzu().contains(&c) {
+                    Shoku::Manzu
+                } else if notation.pinzu().contains(&c) {
+                    Shoku::Pinzu
+                } else {
+                    Shoku::Souzu
+                };
+                let akadora = digit == notation.red_five();
+                let rank = match digit.to_digit(10) {
+                    _ if akadora => 5,
+                    Some(0) | None => return Err(error(ParseErrorReason::InvalidRank(digit))),
+                    Some(rank) => u8::try_from(rank).unwrap(),
+                };
+                Pai::Suupai(Suupai {
+                    shoku,
+                    rank,
+                    akadora,
+                })
+            };
+            if seen[pai] >= 4 {
+                return Err(error(ParseErrorReason::TooManyCopies(pai)));
+            }
+            seen.add(pai);
+            pais.push(pai);
+        }
+    }
+    if !digits.is_empty() {
+        return Err(ParseError {
+            offset: start + tilestring.len(),
+            reason: ParseErrorReason::MissingSuit,
+        });
     }
+    Ok(pais)
+}
+
+/**
+ * Converts tile counts to a tilestring, in the order manzu, pinzu, souzu, jihai.
+ */
+pub fn counts_to_tilestring(counts: &TileCounts) -> String {
+    pais_to_tilestring(counts.to_pais())
+}
+
+/**
+ * Converts tiles to a tilestring, the inverse of `tilestring_to_pais`.
+ *
+ * The tiles are sorted and grouped in the order manzu, pinzu, souzu, jihai. Red fives are
+ * written as 0 and come just before any other fives of their suit.
+ */
+pub fn pais_to_tilestring(pais: impl IntoIterator<Item = Pai>) -> String {
+    pais_to_tilestring_with_notation(pais, &Notation::default())
+}
 
-    let re = Regex::new(r"([0-9]+[mwcpdsb])|([1-7]+[zh])").unwrap();
-    let clumps = re.find_iter(tilestring);
-    let clump_pair_lists = clumps.map(|clump| {
-        let mut clump_string = clump.as_str().to_owned();
-        let letter = clump_string.pop().unwrap();
-        let digits = clump_string.chars();
-        let pairs = digits.map(move |digit| (digit, letter));
-        pairs.collect::<Vec<(char, char)>>()
+/**
+ * Converts tiles to a tilestring written in the given notation.
+ */
+pub fn pais_to_tilestring_with_notation(
+    pais: impl IntoIterator<Item = Pai>,
+    notation: &Notation,
+) -> String {
+    let mut sorted: Vec<Pai> = pais.into_iter().collect();
+    sorted.sort_by_key(|pai| match pai {
+        Pai::Suupai(suupai) => (pai.index(), !suupai.akadora),
+        Pai::Jihai(jihai) => (
+            26 + notation.honor_digit(*jihai).to_digit(10).unwrap() as usize,
+            true,
+        ),
     });
-    let clump_pairs = clump_pair_lists.flatten();
-
-    fn clump_pair_to_pai(dl: (char, char)) -> Option<Pai> {
-        let (digit, letter) = dl;
-        if letter == 'z' || letter == 'h' {
-            match digit {
-                '1' => Some(Pai::Jihai(Jihai::Kazehai(Kazehai::Ton))),
-                '2' => Some(Pai::Jihai(Jihai::Kazehai(Kazehai::Nan))),
-                '3' => Some(Pai::Jihai(Jihai::Kazehai(Kazehai::Shaa))),
-                '4' => Some(Pai::Jihai(Jihai::Kazehai(Kazehai::Pei))),
-                '5' => Some(Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun))),
-                '6' => Some(Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku))),
-                '7' => Some(Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu))),
-                _ => None,
-            }
-        } else {
-            let shoku = match letter {
-                'm' | 'w' | 'c' => Shoku::Manzu,
-                'p' | 'd' => Shoku::Pinzu,
-                's' | 'b' => Shoku::Souzu,
-                _ => return None,
-            };
-            Some(Pai::Suupai(Suupai {
-                shoku,
-                rank: if digit == '0' {
-                    5
+
+    let mut tilestring = String::new();
+    let mut current_letter = None;
+    for pai in sorted {
+        let (digit, letter) = match pai {
+            Pai::Suupai(suupai) => {
+                let letters = match suupai.shoku {
+                    Shoku::Manzu => notation.manzu(),
+                    Shoku::Pinzu => notation.pinzu(),
+                    Shoku::Souzu => notation.souzu(),
+                };
+                let digit = if suupai.akadora {
+                    notation.red_five()
                 } else {
-                    u8::try_from(digit.to_digit(10).unwrap()).unwrap()
-                },
-                akadora: digit == '0',
-            }))
+                    char::from(b'0' + suupai.rank)
+                };
+                (digit, letters[0])
+            }
+            Pai::Jihai(jihai) => (notation.honor_digit(jihai), notation.jihai()[0]),
+        };
+        if current_letter.is_some_and(|l| l != letter) {
+            tilestring.push(current_letter.unwrap());
         }
+        current_letter = Some(letter);
+        tilestring.push(digit);
     }
+    if let Some(letter) = current_letter {
+        tilestring.push(letter);
+    }
+    tilestring
+}
 
-    let pais: Vec<Pai> = clump_pairs
-        .map(|dl| clump_pair_to_pai(dl).unwrap())
-        .collect();
-    Some(pais)
+/**
+ * Converts a tilestring with called melds to free tiles and melds.
+ *
+ * Each meld is written in brackets after the free tiles, e.g. 123m456p11z[l406s][5555z].
+ *
+ * # Melds
+ * * The tiles of a meld are preceded by the seat of the player whose discard was called:
+ *   l for the kamicha, a for the toimen and r for the shimocha.
+ * * The called tile is written first.
+ * * A quad with no seat is a concealed quad.
+ * * A + after the seat makes a quad an added quad, e.g. [a+5555z].
+ * * A seat letter that is also the red five digit of the notation is read as the digit.
+ */
+pub fn tilestring_to_hand(tilestring: &str) -> Result<(Vec<Pai>, Vec<Meld>), ParseError> {
+    tilestring_to_hand_with_notation(tilestring, &Notation::default())
+}
+
+/**
+ * Converts a tilestring with called melds written in the given notation to free tiles and
+ * melds.
+ */
+pub fn tilestring_to_hand_with_notation(
+    tilestring: &str,
+    notation: &Notation,
+) -> Result<(Vec<Pai>, Vec<Meld>), ParseError> {
+    let meld_start = tilestring.find('[').unwrap_or(tilestring.len());
+    let mut seen = TileCounts::new();
+    let free_tiles = parse_pais(&tilestring[..meld_start], 0, notation, &mut seen)?;
+
+    let mut melds = vec![];
+    let mut offset = meld_start;
+    while offset < tilestring.len() {
+        let rest = &tilestring[offset..];
+        let Some(inner) = rest.strip_prefix('[') else {
+            return Err(ParseError {
+                offset,
+                reason: ParseErrorReason::UnclosedMeld,
+            });
+        };
+        let Some(end) = inner.find(']') else {
+            return Err(ParseError {
+                offset,
+                reason: ParseErrorReason::UnclosedMeld,
+            });
+        };
+        let meld = parse_meld(&inner[..end], offset + 1, notation, &mut seen)?;
+        melds.push(meld);
+        offset += end + 2;
+    }
+    Ok((free_tiles, melds))
+}
+
+/**
+ * Converts free tiles and melds to a tilestring, the inverse of `tilestring_to_hand`.
+ */
+pub fn hand_to_tilestring(free_tiles: impl IntoIterator<Item = Pai>, melds: &[Meld]) -> String {
+    hand_to_tilestring_with_notation(free_tiles, melds, &Notation::default())
+}
+
+/**
+ * Converts free tiles and melds to a tilestring written in the given notation.
+ */
+pub fn hand_to_tilestring_with_notation(
+    free_tiles: impl IntoIterator<Item = Pai>,
+    melds: &[Meld],
+    notation: &Notation,
+) -> String {
+    let mut tilestring = pais_to_tilestring_with_notation(free_tiles, notation);
+    for meld in melds {
+        tilestring.push('[');
+        tilestring.push_str(&meld_to_tilestring(meld, notation));
+        tilestring.push(']');
+    }
+    tilestring
+}
+
+/**
+ * Parses a meld, written without brackets, that starts at the given offset of the whole
+ * input.
+ */
+pub(crate) fn parse_meld(
+    tilestring: &str,
+    start: usize,
+    notation: &Notation,
+    seen: &mut TileCounts,
+) -> Result<Meld, ParseError> {
+    let mut chars = tilestring.char_indices().peekable();
+    let from = match chars.peek() {
+        Some((_, c)) if notation.is_digit(*c) => None,
+        Some((_, 'l')) => Some(RelativeSeat::Kamicha),
+        Some((_, 'a')) => Some(RelativeSeat::Toimen),
+        Some((_, 'r')) => Some(RelativeSeat::Shimocha),
+        _ => None,
+    };
+    if from.is_some() {
+        chars.next();
+    }
+    let added = chars.next_if(|(_, c)| *c == '+').is_some();
+    let tiles_start = chars.peek().map_or(tilestring.len(), |(offset, _)| *offset);
+    let tiles = parse_pais(
+        &tilestring[tiles_start..],
+        start + tiles_start,
+        notation,
+        seen,
+    )?;
+
+    let invalid_call = ParseError {
+        offset: start,
+        reason: ParseErrorReason::InvalidCall,
+    };
+    let call = match (from, tiles.first()) {
+        (None, _) if added => return Err(invalid_call),
+        (None, _) => Call::Ankan,
+        (Some(_), None) => return Err(invalid_call),
+        (Some(from), Some(&called)) if added => Call::Shouminkan { called, from },
+        (Some(from), Some(&called)) if tiles.len() == 4 => Call::Daiminkan { called, from },
+        (Some(from), Some(&called)) if tiles.iter().all(|t| *t == called) => {
+            Call::Pon { called, from }
+        }
+        (Some(RelativeSeat::Kamicha), Some(&called)) => Call::Chi { called },
+        (Some(_), Some(_)) => return Err(invalid_call),
+    };
+    Meld::new(call, tiles).map_err(|error| ParseError {
+        offset: start,
+        reason: ParseErrorReason::InvalidMeld(error),
+    })
+}
+
+/**
+ * Writes a meld without brackets, the inverse of `parse_meld`.
+ */
+pub(crate) fn meld_to_tilestring(meld: &Meld, notation: &Notation) -> String {
+    let call = meld.call();
+    let mut tilestring = String::new();
+    tilestring.push(match call.from() {
+        Some(RelativeSeat::Kamicha) => 'l',
+        Some(RelativeSeat::Toimen) => 'a',
+        Some(RelativeSeat::Shimocha) => 'r',
+        None => return pais_to_tilestring_with_notation(meld.tiles().to_owned(), notation),
+    });
+    if let Call::Shouminkan { .. } = call {
+        tilestring.push('+');
+    }
+    let mut rest = meld.tiles().to_owned();
+    if let Some(called) = call.called_tile() {
+        let position = rest
+            .iter()
+            .position(|t| *t == called && t.is_akadora() == called.is_akadora())
+            .unwrap();
+        rest.remove(position);
+        let mut called_tilestring = pais_to_tilestring_with_notation([called], notation);
+        called_tilestring.pop();
+        tilestring.push_str(&called_tilestring);
+    }
+    tilestring.push_str(&pais_to_tilestring_with_notation(rest, notation));
+    tilestring
 }
 
 #[cfg(test)]
+// The tests spell out the expected boolean for readability.
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
     use super::*;
 
@@ -158,6 +564,234 @@ mod tests {
         assert_eq!(is_tilestring("helloworld"), false);
     }
 
+    #[test]
+    fn tilestrings_are_valid_exactly_when_they_parse() {
+        assert_eq!(is_tilestring("55555m"), false);
+        assert_eq!(is_tilestring("123m[a555z]"), true);
+        assert_eq!(is_tilestring("123m[a55z]"), false);
+        assert_eq!(
+            is_tilestring_with_notation("123m", &Notation::tenhou()),
+            true
+        );
+        assert_eq!(
+            is_tilestring_with_notation("123w", &Notation::tenhou()),
+            false
+        );
+    }
+
+    #[test]
+    fn counts_round_trip_through_tilestrings() {
+        let counts = tilestring_to_counts("789s33377z111406p").unwrap();
+        assert_eq!(counts_to_tilestring(&counts), "111456p789s33377z");
+    }
+
+    #[test]
+    fn converts_pais_to_canonical_tilestrings() {
+        let pais = tilestring_to_pais("789s33377z111406p").unwrap();
+        assert_eq!(pais_to_tilestring(pais), "111406p789s33377z");
+    }
+
+    #[test]
+    fn red_fives_come_before_normal_fives() {
+        let pais = tilestring_to_pais("505m").unwrap();
+        assert_eq!(pais_to_tilestring(pais), "055m");
+    }
+
+    #[test]
+    fn pais_round_trip_through_tilestrings() {
+        let pais = tilestring_to_pais("1234567z19m19p0s").unwrap();
+        let tilestring = pais_to_tilestring(pais.to_owned());
+        assert_eq!(tilestring, "19m19p0s1234567z");
+        let round_tripped = tilestring_to_pais(&tilestring).unwrap();
+        assert_eq!(pais_to_tilestring(round_tripped), tilestring);
+    }
+
+    #[test]
+    fn no_pais_is_an_empty_tilestring() {
+        assert_eq!(pais_to_tilestring(vec![]), "");
+    }
+
+    #[test]
+    fn hands_with_melds_round_trip_through_tilestrings() {
+        let tilestring = "11z456p123m[l640s][5555z][a+7777z]";
+        let (free_tiles, melds) = tilestring_to_hand(tilestring).unwrap();
+        assert_eq!(free_tiles.len(), 8);
+        assert_eq!(melds.len(), 3);
+        assert_eq!(
+            melds[0].call(),
+            Call::Chi {
+                called: "6s".parse().unwrap()
+            }
+        );
+        assert_eq!(melds[1].call(), Call::Ankan);
+        assert_eq!(melds[2].call().from(), Some(RelativeSeat::Toimen));
+        assert!(matches!(melds[2].call(), Call::Shouminkan { .. }));
+        assert_eq!(
+            hand_to_tilestring(free_tiles, &melds),
+            "123m456p11z[l640s][5555z][a+7777z]"
+        );
+    }
+
+    #[test]
+    fn red_called_tile_round_trips() {
+        let (free_tiles, melds) = tilestring_to_hand("[r055p]").unwrap();
+        assert!(matches!(
+            melds[0].call(),
+            Call::Pon {
+                called: Pai::Suupai(Suupai { akadora: true, .. }),
+                from: RelativeSeat::Shimocha
+            }
+        ));
+        assert_eq!(hand_to_tilestring(free_tiles, &melds), "[r055p]");
+    }
+
+    #[test]
+    fn meld_tiles_must_form_a_group() {
+        let reason = |tilestring| tilestring_to_hand(tilestring).unwrap_err().reason;
+        assert_eq!(
+            reason("123m[l135s]"),
+            ParseErrorReason::InvalidMeld(MeldError::WrongMentsu)
+        );
+        assert_eq!(
+            reason("123m[123s]"),
+            ParseErrorReason::InvalidMeld(MeldError::WrongMentsu)
+        );
+        assert_eq!(reason("123m[a123s]"), ParseErrorReason::InvalidCall);
+        assert_eq!(reason("123m[+1111s]"), ParseErrorReason::InvalidCall);
+        assert_eq!(reason("123m[l123s"), ParseErrorReason::UnclosedMeld);
+        assert_eq!(reason("123m[l123s]4p"), ParseErrorReason::UnclosedMeld);
+        assert_eq!(
+            reason("[]"),
+            ParseErrorReason::InvalidMeld(MeldError::WrongMentsu)
+        );
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_problem() {
+        let error = |tilestring| tilestring_to_pais(tilestring).unwrap_err();
+        assert_eq!(
+            error("123m45x"),
+            ParseError {
+                offset: 6,
+                reason: ParseErrorReason::UnknownSuit('x')
+            }
+        );
+        assert_eq!(
+            error("123m48z"),
+            ParseError {
+                offset: 5,
+                reason: ParseErrorReason::InvalidHonorDigit('8')
+            }
+        );
+        assert_eq!(
+            error("10z"),
+            ParseError {
+                offset: 1,
+                reason: ParseErrorReason::RedFiveHonor
+            }
+        );
+        assert_eq!(
+            error("123m45"),
+            ParseError {
+                offset: 6,
+                reason: ParseErrorReason::MissingSuit
+            }
+        );
+        assert_eq!(
+            error("m"),
+            ParseError {
+                offset: 0,
+                reason: ParseErrorReason::MissingDigits
+            }
+        );
+        assert_eq!(
+            error("12mp"),
+            ParseError {
+                offset: 3,
+                reason: ParseErrorReason::MissingDigits
+            }
+        );
+        assert_eq!(
+            error("123m45").to_string(),
+            "digits without a suit at offset 6"
+        );
+    }
+
+    #[test]
+    fn a_fifth_copy_is_an_error() {
+        let pai = "5p".parse().unwrap();
+        assert_eq!(
+            tilestring_to_pais("0555p5p").unwrap_err(),
+            ParseError {
+                offset: 5,
+                reason: ParseErrorReason::TooManyCopies(pai)
+            }
+        );
+        assert_eq!(
+            tilestring_to_hand("55p[0555p]").unwrap_err(),
+            ParseError {
+                offset: 6,
+                reason: ParseErrorReason::TooManyCopies(pai)
+            }
+        );
+    }
+
+    #[test]
+    fn tenhou_notation_orders_dragons_differently() {
+        let tenhou = Notation::tenhou();
+        let pais = tilestring_to_pais_with_notation("567z", &tenhou).unwrap();
+        assert_eq!(
+            pais,
+            vec![
+                Pai::Jihai(Jihai::Sangenpai(Sangenpai::Haku)),
+                Pai::Jihai(Jihai::Sangenpai(Sangenpai::Hatsu)),
+                Pai::Jihai(Jihai::Sangenpai(Sangenpai::Chun)),
+            ]
+        );
+        assert_eq!(pais_to_tilestring(pais.to_owned()), "567z");
+        let chun_first = vec![pais[2], pais[0], pais[1]];
+        assert_eq!(
+            pais_to_tilestring_with_notation(chun_first, &tenhou),
+            "567z"
+        );
+    }
+
+    #[test]
+    fn tenhou_notation_only_accepts_its_own_letters() {
+        let tenhou = Notation::tenhou();
+        assert_eq!(
+            tilestring_to_pais_with_notation("123w", &tenhou)
+                .unwrap_err()
+                .reason,
+            ParseErrorReason::UnknownSuit('w')
+        );
+    }
+
+    #[test]
+    fn red_five_digit_is_configurable() {
+        let notation = Notation::new(
+            vec!['m'],
+            vec!['p'],
+            vec!['s'],
+            vec!['z'],
+            Notation::default().honors(),
+            'r',
+        )
+        .unwrap();
+        let (free_tiles, melds) =
+            tilestring_to_hand_with_notation("r5m[l4r6p]", &notation).unwrap();
+        assert_eq!(
+            hand_to_tilestring_with_notation(free_tiles, &melds, &notation),
+            "r5m[l4r6p]"
+        );
+        assert_eq!(
+            tilestring_to_pais_with_notation("0m", &notation)
+                .unwrap_err()
+                .reason,
+            ParseErrorReason::InvalidRank('0')
+        );
+    }
+
     #[test]
     fn converts_tilestrings_to_pais() {
         let pais = tilestring_to_pais("111406p33377z789s").unwrap();
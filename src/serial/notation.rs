@@ -0,0 +1,300 @@
+/*!
+ * Conventions for writing tiles as tilestrings.
+ */
+
+use crate::rules::*;
+
+use core::fmt;
+
+/**
+ * Why letters and digits cannot make a notation.
+ */
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NotationError {
+    /**
+     * A suit or the honors have no letters.
+     */
+    NoLetters,
+
+    /**
+     * A character that would stand for two things, such as a letter of two suits, a letter
+     * that is also a digit, or a red five digit from 1 through 9.
+     */
+    AmbiguousCharacter(char),
+
+    /**
+     * An honor that is not among the seven digits.
+     */
+    MissingHonor(Jihai),
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotationError::NoLetters => write!(f, "each suit needs at least one letter"),
+            NotationError::AmbiguousCharacter(c) => write!(f, "'{c}' has more than one meaning"),
+            NotationError::MissingHonor(jihai) => write!(f, "no digit is {jihai}"),
+        }
+    }
+}
+
+impl std::error::Error for NotationError {}
+
+/**
+ * The letters and digits that a tilestring uses for each tile.
+ *
+ * The first letter of each suit is the one written when serializing; the others are only
+ * accepted when parsing.
+ */
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Notation {
+    manzu: Vec<char>,
+    pinzu: Vec<char>,
+    souzu: Vec<char>,
+    jihai: Vec<char>,
+    honors: [Jihai; 7],
+    red_five: char,
+}
+
+impl Notation {
+    /**
+     * A notation with the given letters and digits, if every character has one meaning.
+     *
+     * # Arguments
+     *
+     * * `manzu`, `pinzu`, `souzu`, `jihai`: The letters for each suit and for the honors,
+     *   the first of which is written when serializing.
+     * * `honors`: The honors represented by the digits 1 to 7, each exactly once.
+     * * `red_five`: The digit for a red five.
+     */
+    pub fn new(
+        manzu: Vec<char>,
+        pinzu: Vec<char>,
+        souzu: Vec<char>,
+        jihai: Vec<char>,
+        honors: [Jihai; 7],
+        red_five: char,
+    ) -> Result<Self, NotationError> {
+        let notation = Notation {
+            manzu,
+            pinzu,
+            souzu,
+            jihai,
+            honors,
+            red_five,
+        };
+        let letter_lists = [
+            &notation.manzu,
+            &notation.pinzu,
+            &notation.souzu,
+            &notation.jihai,
+        ];
+        if letter_lists.iter().any(|letters| letters.is_empty()) {
+            return Err(NotationError::NoLetters);
+        }
+        if ('1'..='9').contains(&red_five) {
+            return Err(NotationError::AmbiguousCharacter(red_five));
+        }
+        let mut seen = vec![];
+        for c in letter_lists.into_iter().flatten() {
+            if notation.is_digit(*c) || seen.contains(c) {
+                return Err(NotationError::AmbiguousCharacter(*c));
+            }
+            seen.push(*c);
+        }
+        for pai in crate::rules::honors() {
+            if let Pai::Jihai(jihai) = pai {
+                if !notation.honors.contains(&jihai) {
+                    return Err(NotationError::MissingHonor(jihai));
+                }
+            }
+        }
+        Ok(notation)
+    }
+
+    /**
+     * The notation of Tenhou and Mahjong Soul, where 5z is haku, 6z is hatsu and 7z is chun.
+     */
+    pub fn tenhou() -> Self {
+        Notation {
+            manzu: vec!['m'],
+            pinzu: vec!['p'],
+            souzu: vec!['s'],
+            jihai: vec!['z'],
+            honors: [
+                Jihai::Kazehai(Kazehai::Ton),
+                Jihai::Kazehai(Kazehai::Nan),
+                Jihai::Kazehai(Kazehai::Shaa),
+                Jihai::Kazehai(Kazehai::Pei),
+                Jihai::Sangenpai(Sangenpai::Haku),
+                Jihai::Sangenpai(Sangenpai::Hatsu),
+                Jihai::Sangenpai(Sangenpai::Chun),
+            ],
+            red_five: '0',
+        }
+    }
+
+    /**
+     * The letters for manzu.
+     */
+    pub fn manzu(&self) -> &[char] {
+        &self.manzu
+    }
+
+    /**
+     * The letters for pinzu.
+     */
+    pub fn pinzu(&self) -> &[char] {
+        &self.pinzu
+    }
+
+    /**
+     * The letters for souzu.
+     */
+    pub fn souzu(&self) -> &[char] {
+        &self.souzu
+    }
+
+    /**
+     * The letters for jihai.
+     */
+    pub fn jihai(&self) -> &[char] {
+        &self.jihai
+    }
+
+    /**
+     * The honors represented by the digits 1 to 7.
+     */
+    pub fn honors(&self) -> [Jihai; 7] {
+        self.honors
+    }
+
+    /**
+     * The digit for a red five.
+     */
+    pub fn red_five(&self) -> char {
+        self.red_five
+    }
+
+    /**
+     * Whether the character is a suit letter.
+     */
+    pub fn is_suit_letter(&self, c: char) -> bool {
+        [&self.manzu, &self.pinzu, &self.souzu, &self.jihai]
+            .iter()
+            .any(|letters| letters.contains(&c))
+    }
+
+    /**
+     * Whether the character is a digit standing for a tile.
+     */
+    pub fn is_digit(&self, c: char) -> bool {
+        c.is_ascii_digit() || c == self.red_five
+    }
+
+    /**
+     * The honor written as the given digit.
+     */
+    pub fn honor(&self, digit: char) -> Option<Jihai> {
+        let position = digit.to_digit(10)?.checked_sub(1)?;
+        self.honors.get(position as usize).copied()
+    }
+
+    /**
+     * The digit that the given honor is written as.
+     */
+    pub fn honor_digit(&self, jihai: Jihai) -> char {
+        let position = self
+            .honors
+            .iter()
+            .position(|honor| *honor == jihai)
+            .unwrap();
+        char::from(b'1' + u8::try_from(position).unwrap())
+    }
+}
+
+/**
+ * The notation used by this crate, where 5z is chun, 6z is haku and 7z is hatsu.
+ *
+ * Manzu may also be written with w or c, pinzu with d, souzu with b and jihai with h.
+ */
+impl Default for Notation {
+    fn default() -> Self {
+        Notation {
+            manzu: vec!['m', 'w', 'c'],
+            pinzu: vec!['p', 'd'],
+            souzu: vec!['s', 'b'],
+            jihai: vec!['z', 'h'],
+            honors: [
+                Jihai::Kazehai(Kazehai::Ton),
+                Jihai::Kazehai(Kazehai::Nan),
+                Jihai::Kazehai(Kazehai::Shaa),
+                Jihai::Kazehai(Kazehai::Pei),
+                Jihai::Sangenpai(Sangenpai::Chun),
+                Jihai::Sangenpai(Sangenpai::Haku),
+                Jihai::Sangenpai(Sangenpai::Hatsu),
+            ],
+            red_five: '0',
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn honor_digits_round_trip() {
+        for notation in [Notation::default(), Notation::tenhou()] {
+            for digit in '1'..='7' {
+                let honor = notation.honor(digit).unwrap();
+                assert_eq!(notation.honor_digit(honor), digit);
+            }
+            assert_eq!(notation.honor('0'), None);
+            assert_eq!(notation.honor('8'), None);
+        }
+    }
+
+    #[test]
+    fn every_character_must_have_one_meaning() {
+        let honors = Notation::default().honors();
+        let notation = |manzu: Vec<char>, souzu: Vec<char>, red_five: char| {
+            Notation::new(manzu, vec!['p'], souzu, vec!['z'], honors, red_five)
+        };
+        assert!(notation(vec!['m'], vec!['s'], 'r').is_ok());
+        assert_eq!(
+            notation(vec![], vec!['s'], '0'),
+            Err(NotationError::NoLetters)
+        );
+        assert_eq!(
+            notation(vec!['m', 's'], vec!['s'], '0'),
+            Err(NotationError::AmbiguousCharacter('s'))
+        );
+        assert_eq!(
+            notation(vec!['m'], vec!['s'], '5'),
+            Err(NotationError::AmbiguousCharacter('5'))
+        );
+        assert_eq!(
+            notation(vec!['m'], vec!['r'], 'r'),
+            Err(NotationError::AmbiguousCharacter('r'))
+        );
+        let mut repeated = honors;
+        repeated[6] = repeated[5];
+        assert_eq!(
+            Notation::new(vec!['m'], vec!['p'], vec!['s'], vec!['z'], repeated, '0'),
+            Err(NotationError::MissingHonor(honors[6]))
+        );
+    }
+
+    #[test]
+    fn tenhou_dragons_are_in_a_different_order() {
+        assert_eq!(
+            Notation::tenhou().honor('7'),
+            Some(Jihai::Sangenpai(Sangenpai::Chun))
+        );
+        assert_eq!(
+            Notation::default().honor('7'),
+            Some(Jihai::Sangenpai(Sangenpai::Hatsu))
+        );
+    }
+}